@@ -0,0 +1,152 @@
+//! Collects the solutions in `src/bin/` into `$OUT_DIR/solutions.rs`, which the main binary includes
+//! as modules. This lets `cargo all` run every day in-process instead of spawning one binary per day.
+//!
+//! With the `embed-inputs` feature, also embeds the inputs and examples into `$OUT_DIR/inputs.rs`.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // solutions are named `DD.rs`, or `YYYY-DD.rs` for years other than the configured one.
+    let mut solutions: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let day = match stem.split_once('-') {
+                        Some((year, day)) if year.len() == 4 && year.parse::<u16>().is_ok() => day,
+                        Some(_) => return None,
+                        None => &stem,
+                    };
+                    let is_solution = path.extension()? == "rs"
+                        && day.len() == 2
+                        && day.parse::<u8>().is_ok_and(|x| (1..=25).contains(&x));
+                    is_solution.then(|| (stem.replace('-', "_"), path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    solutions.sort_unstable();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (name, path) in &solutions {
+        modules.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code)]\nmod day_{name};\n"
+        ));
+        entries.push_str(&format!("    day_{name}::SOLUTION,\n"));
+    }
+
+    let out = format!(
+        "{modules}\n/// Every solution in `src/bin/`, ordered by year and day.\npub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[\n{entries}];\n"
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        embed_inputs(Path::new(&manifest_dir));
+    }
+}
+
+/// Writes the inputs and examples as a list of `(path, contents)`, where the path is relative to
+/// the project root, e.g. `data/inputs/05.txt`. Only the folders of inputs and examples that are
+/// configured in `aoc.toml` are embedded, and those of other years next to them.
+///
+/// All files go into the library, as `solution!` cannot `include_str!` the files of its day: the
+/// input does not exist before it is downloaded, which would fail the build.
+fn embed_inputs(manifest_dir: &Path) {
+    let mut files = vec![];
+
+    for folder in data_folders(manifest_dir) {
+        collect_text_files(manifest_dir, &folder, &mut files);
+
+        // other years are scoped by year, e.g. `data/2022/inputs`.
+        let (Some(parent), Some(name)) = (folder.parent(), folder.file_name()) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(manifest_dir.join(parent)) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            let year = entry.file_name().to_string_lossy().to_string();
+            if year.len() == 4 && year.parse::<u16>().is_ok() {
+                collect_text_files(manifest_dir, &parent.join(year).join(name), &mut files);
+            }
+        }
+    }
+
+    files.sort_unstable();
+
+    let mut entries = String::new();
+
+    for path in &files {
+        let key = path
+            .components()
+            .map(|x| x.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let full_path = manifest_dir.join(path);
+        println!("cargo:rerun-if-changed={}", full_path.display());
+        entries.push_str(&format!(
+            "    ({key:?}, include_str!({:?})),\n",
+            full_path.display().to_string()
+        ));
+    }
+
+    let out = format!(
+        "/// The data files that were embedded at build time, keyed by their path in the project.\npub const EMBEDDED: &[(&str, &str)] = &[\n{entries}];\n"
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("inputs.rs");
+    fs::write(out_path, out).unwrap();
+}
+
+/// The folders of inputs and examples as configured in the `[paths]` of `aoc.toml`, which the
+/// library resolves the same way, see `config::Paths`.
+fn data_folders(manifest_dir: &Path) -> Vec<PathBuf> {
+    let config_path = manifest_dir.join("aoc.toml");
+    println!("cargo:rerun-if-changed={}", config_path.display());
+
+    let paths = fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|x| x.parse::<toml::Table>().ok())
+        .and_then(|x| x.get("paths")?.as_table().cloned())
+        .unwrap_or_default();
+
+    ["inputs", "examples"]
+        .into_iter()
+        .map(|kind| match paths.get(kind).and_then(|x| x.as_str()) {
+            Some(folder) => PathBuf::from(folder),
+            None => Path::new("data").join(kind),
+        })
+        .collect()
+}
+
+/// Collects the text files in `folder`, relative to the project root. Subfolders are left out.
+fn collect_text_files(manifest_dir: &Path, folder: &Path, files: &mut Vec<PathBuf>) {
+    let dir = manifest_dir.join(folder);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_file() && path.extension().is_some_and(|x| x == "txt") {
+            files.push(folder.join(entry.file_name()));
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use itertools::Itertools;
-use std::{ops::Range, usize};
+use std::ops::Range;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse);
 
 #[derive(Debug, PartialEq)]
-struct Almanac {
+pub struct Almanac {
     mappings: Vec<TransferFunction>,
 }
 
@@ -26,17 +26,6 @@ impl Almanac {
         (seeds, Almanac { mappings })
     }
 
-    fn from_input_range(input: &str) -> (SeedRanges, Almanac) {
-        let mut lines = input.split("\n\n");
-        let seedline: &str = lines.next().unwrap();
-
-        let seeds: SeedRanges = SeedRanges::from_line(seedline);
-
-        let mappings: Vec<TransferFunction> = lines.map(TransferFunction::from_lines).collect();
-
-        (seeds, Almanac { mappings })
-    }
-
     fn map_seed(&self, i: usize) -> usize {
         let mut i = i;
 
@@ -54,18 +43,10 @@ struct SeedRanges {
 }
 
 impl SeedRanges {
-    fn from_line(line: &str) -> SeedRanges {
+    fn from_seeds(nums: &[usize]) -> SeedRanges {
         let mut ranges: Vec<Range<usize>> = vec![];
         let mut endpoints: Vec<usize> = vec![];
 
-        let nums: Vec<usize> = line
-            .split_once(": ")
-            .unwrap()
-            .1
-            .split_whitespace()
-            .map(|f| f.parse().unwrap())
-            .collect();
-
         for s in nums.chunks(2) {
             ranges.push((s[0])..(s[0] + s[1]));
             endpoints.push(s[0]);
@@ -167,16 +148,18 @@ impl MapComponent {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (seeds, almanac) = Almanac::from_input(input);
+pub fn parse(input: &str) -> (Vec<usize>, Almanac) {
+    Almanac::from_input(input)
+}
 
+pub fn part_one((seeds, almanac): &(Vec<usize>, Almanac)) -> Option<usize> {
     let locations: Vec<usize> = seeds.iter().map(|f| almanac.map_seed(*f)).collect();
 
     Some(*locations.iter().min().unwrap())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (seeds, almanac) = Almanac::from_input_range(input);
+pub fn part_two((seeds, almanac): &(Vec<usize>, Almanac)) -> Option<usize> {
+    let seeds = SeedRanges::from_seeds(seeds);
 
     let mut dc = vec![0, usize::MAX];
 
@@ -202,13 +185,17 @@ mod test {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(46));
     }
 }
@@ -46,47 +46,6 @@ impl Race {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        // let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
-        assert_eq!(result, Some(71503));
-    }
-    #[test]
-    fn test_part_one_parse() {
-        let races = parse_bad_kerning(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(
-            *races.iter().last().unwrap(),
-            Race {
-                race_time: 30,
-                record_distance: 200
-            }
-        );
-    }
-
-    #[test]
-    fn test_part_two_parse() {
-        let race = parse_correctly(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(
-            race,
-            Race {
-                race_time: 71530,
-                record_distance: 940200
-            }
-        );
-    }
-}
-
 type IntSize = u128;
 
 fn parse_bad_kerning(input: &str) -> Vec<Race> {
@@ -134,3 +93,44 @@ fn parse_correctly(input: &str) -> Race {
         record_distance: distance,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(288));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        // let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+        assert_eq!(result, Some(71503));
+    }
+    #[test]
+    fn test_part_one_parse() {
+        let races = parse_bad_kerning(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(
+            *races.iter().last().unwrap(),
+            Race {
+                race_time: 30,
+                record_distance: 200
+            }
+        );
+    }
+
+    #[test]
+    fn test_part_two_parse() {
+        let race = parse_correctly(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(
+            race,
+            Race {
+                race_time: 71530,
+                record_distance: 940200
+            }
+        );
+    }
+}
@@ -168,7 +168,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     let s: usize = cards
         .iter()
         .enumerate()
-        .map(|(index, value)| (value.bid * (index + 1)))
+        .map(|(index, value)| value.bid * (index + 1))
         .sum();
 
     Some(s)
@@ -186,7 +186,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     let s: usize = cards
         .iter()
         .enumerate()
-        .map(|(index, value)| (value.bid * (index + 1)))
+        .map(|(index, value)| value.bid * (index + 1))
         .sum();
 
     Some(s)
@@ -68,7 +68,7 @@ enum Errors {
 
 fn mapping_from_str(line: &str) -> Result<(&str, [&str; 2]), Errors> {
     let parts: Vec<&str> = line
-        .split(|c| c == '=' || c == '(' || c == ',' || c == ')')
+        .split(['=', '(', ',', ')'])
         .filter(|word| !word.trim().is_empty())
         .collect();
 
@@ -89,7 +89,9 @@ enum Direction {
     Right,
 }
 
-fn parse_input(input: &str) -> Result<(Vec<Direction>, HashMap<&str, [&str; 2]>), Errors> {
+type Network<'a> = HashMap<&'a str, [&'a str; 2]>;
+
+fn parse_input(input: &str) -> Result<(Vec<Direction>, Network<'_>), Errors> {
     let lines: Vec<&str> = input.split('\n').collect();
 
     let mut line_it = lines.iter();
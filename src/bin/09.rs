@@ -1,7 +1,8 @@
 advent_of_code::solution!(9);
 
+#[allow(clippy::vec_box)]
 fn parse_input(input: &str) -> Vec<Box<Sequence>> {
-    let input_lines: Vec<&str> = input.split('\n').filter(|f| f.len() > 0).collect();
+    let input_lines: Vec<&str> = input.split('\n').filter(|f| !f.is_empty()).collect();
     let vectors: Vec<Vec<isize>> = input_lines
         .iter()
         .map(|f| {
@@ -58,7 +59,7 @@ impl Sequence {
         }
     }
 
-    fn get_differential(values: &Vec<isize>, depth: usize) -> Box<Sequence> {
+    fn get_differential(values: &[isize], depth: usize) -> Box<Sequence> {
         let diff: Vec<isize> = values
             .windows(2)
             .filter_map(|f| match f {
@@ -1,4 +1,4 @@
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, parse);
 
 use grid::Grid;
 use itertools::Itertools;
@@ -23,7 +23,7 @@ impl Direction {
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum Tile {
+pub enum Tile {
     Horizontal,
     Vertical,
     UR,
@@ -63,7 +63,7 @@ impl Tile {
     }
 }
 
-fn parse_to_grid(input: &str) -> Grid<Tile> {
+pub fn parse(input: &str) -> Grid<Tile> {
     let n: usize = input.split('\n').next().unwrap().len();
     let chars: Vec<Tile> = input
         .chars()
@@ -110,12 +110,11 @@ fn find_initial_direction(g: &Grid<Tile>, idx: (usize, usize)) -> Option<Directi
     None
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let g = parse_to_grid(input);
+pub fn part_one(g: &Grid<Tile>) -> Option<u32> {
     let mut i: u32 = 0;
-    let mut idx = find_start(&g).unwrap();
+    let mut idx = find_start(g).unwrap();
     let mut tile: Tile;
-    let mut dir: Direction = find_initial_direction(&g, idx).unwrap();
+    let mut dir: Direction = find_initial_direction(g, idx).unwrap();
 
     loop {
         dir.update_index(&mut idx);
@@ -144,11 +143,10 @@ fn shoelace(x: Vec<usize>, y: Vec<usize>) -> usize {
     lhs.abs_diff(rhs) / 2
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let g = parse_to_grid(input);
-    let mut idx = find_start(&g).unwrap();
+pub fn part_two(g: &Grid<Tile>) -> Option<usize> {
+    let mut idx = find_start(g).unwrap();
     let mut tile: Tile;
-    let mut dir: Direction = find_initial_direction(&g, idx).unwrap();
+    let mut dir: Direction = find_initial_direction(g, idx).unwrap();
 
     let mut x: Vec<usize> = vec![idx.0];
     let mut y: Vec<usize> = vec![idx.1];
@@ -180,52 +178,52 @@ mod tests {
 
     #[test]
     fn test_part_one_simple() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 1,
-        ));
+        )));
         assert_eq!(result, Some(8));
     }
     #[test]
     fn test_part_one_complex() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        )));
         assert_eq!(result, Some(4));
     }
 
     #[test]
     fn test_part_two_small() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        )));
         assert_eq!(result, Some(1));
 
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 3,
-        ));
+        )));
         assert_eq!(result, Some(4));
     }
 
     #[test]
     fn test_part_two_med() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 3,
-        ));
+        )));
         assert_eq!(result, Some(4));
     }
 
     #[test]
     fn test_part_two_large() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 4,
-        ));
+        )));
         assert_eq!(result, Some(8));
     }
     #[test]
     fn test_part_two_xl() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 5,
-        ));
+        )));
         assert_eq!(result, Some(10));
     }
 }
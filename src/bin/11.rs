@@ -1,5 +1,6 @@
 advent_of_code::solution!(11);
 
+use advent_of_code::template::params::param;
 use itertools::Itertools;
 
 #[derive(Debug, Clone, Copy)]
@@ -89,7 +90,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    solve_general(input, 1_000_000)
+    solve_general(input, param("expansion", 1_000_000))
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, parse);
 
+use advent_of_code::template::params::param;
 use cached::proc_macro::cached;
 use std::str::FromStr;
 
@@ -30,6 +31,10 @@ impl TryFrom<char> for Spring {
 #[derive(Debug)]
 enum RowError {}
 
+/// The condition records of all rows.
+#[derive(Debug)]
+pub struct Records(Vec<Row>);
+
 #[derive(Debug)]
 struct Row {
     springs: Vec<Spring>,
@@ -53,14 +58,8 @@ impl FromStr for Row {
 }
 
 impl Row {
-    fn from_str_expanded(s: &str, n: usize) -> Result<Self, RowError> {
-        let (spring_string, damage) = s.split_once(' ').unwrap();
-
-        let spring_base: Vec<Spring> = spring_string
-            .chars()
-            .map(|f| Spring::try_from(f).unwrap())
-            .collect();
-        let groups_base: Vec<usize> = damage.split(',').map(|f| f.parse().unwrap()).collect();
+    fn expanded(&self, n: usize) -> Row {
+        let (spring_base, groups_base) = (&self.springs, &self.groups);
 
         let mut springs: Vec<Spring> = Vec::with_capacity(spring_base.len() * n);
         let mut groups: Vec<usize> = Vec::with_capacity(spring_base.len() * n + (n - 1));
@@ -74,7 +73,7 @@ impl Row {
             groups.extend_from_slice(&groups_base[..]);
         }
 
-        Ok(Row { springs, groups })
+        Row { springs, groups }
     }
 }
 
@@ -128,26 +127,29 @@ fn recurse_ordering(springs: Vec<Spring>, groups: Vec<usize>) -> usize {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let a: Vec<Row> = input
-        .split('\n')
-        .filter(|f| !f.is_empty())
-        .map(|f| Row::from_str(f).unwrap())
-        .collect();
+pub fn parse(input: &str) -> Records {
+    Records(
+        input
+            .split('\n')
+            .filter(|f| !f.is_empty())
+            .map(|f| Row::from_str(f).unwrap())
+            .collect(),
+    )
+}
 
+pub fn part_one(records: &Records) -> Option<usize> {
     Some(
-        a.iter()
+        records
+            .0
+            .iter()
             .map(|f| recurse_ordering(f.springs.clone(), f.groups.clone()))
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let a: Vec<Row> = input
-        .split('\n')
-        .filter(|f| !f.is_empty())
-        .map(|f| Row::from_str_expanded(f, 5).unwrap())
-        .collect();
+pub fn part_two(records: &Records) -> Option<usize> {
+    let unfold = param("unfold", 5);
+    let a: Vec<Row> = records.0.iter().map(|f| f.expanded(unfold)).collect();
     Some(
         a.iter()
             .map(|f| recurse_ordering(f.springs.clone(), f.groups.clone()))
@@ -161,13 +163,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(525152));
     }
 }
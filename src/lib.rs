@@ -1 +1,12 @@
 pub mod template;
+
+#[cfg(all(feature = "dhat-heap", feature = "count-allocations"))]
+compile_error!("the `dhat-heap` and `count-allocations` features cannot be enabled together");
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "count-allocations", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: template::alloc::CountingAllocator = template::alloc::CountingAllocator;
@@ -1,31 +1,65 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, bench_diff, cross_validate, download, examples, history, progress, read, scaffold, solve,
+    verify,
+};
 use args::{parse, AppArguments};
+use solutions::SOLUTIONS;
+
+/// The solutions of all days, compiled into this binary as modules by `build.rs`.
+#[cfg(not(test))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// The tests of each day run as part of its own binary, so they are not included twice.
+#[cfg(test)]
+mod solutions {
+    pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[];
+}
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        commands::{all, bench_diff, solve},
+        config,
+        input::Source,
+        runner::BenchConfig,
+        Puzzle, Year,
+    };
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
+        },
+        Examples {
+            puzzle: Puzzle,
+        },
+        History {
+            puzzle: Puzzle,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
         },
         Solve {
-            day: Day,
-            release: bool,
-            time: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            puzzle: Puzzle,
+            options: solve::Options,
         },
         All {
-            release: bool,
-            time: bool,
+            options: all::Options,
+        },
+        BenchDiff {
+            options: bench_diff::Options,
+        },
+        Verify {
+            year: Year,
+        },
+        Progress {
+            year: Year,
         },
     }
 
@@ -34,25 +68,72 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                options: all::Options {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    stat: args.opt_value_from_str("--stat")?.unwrap_or_default(),
+                    bench: parse_bench_config(&mut args)?,
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    jobs: args.opt_value_from_str("--jobs")?,
+                    solved_only: args.contains("--solved-only"),
+                    changed: args.contains("--changed"),
+                    allocs: args.contains("--allocs"),
+                    timeout: parse_timeout(&mut args)?,
+                    year: parse_year(&mut args)?,
+                    // free arguments are parsed last, after all flags have been consumed.
+                    days: args.opt_free_from_str()?.unwrap_or_default(),
+                },
+            },
+            Some("bench-diff") => AppArguments::BenchDiff {
+                options: bench_diff::Options {
+                    save_baseline: args.opt_value_from_str("--save-baseline")?,
+                    threshold: args
+                        .opt_value_from_str("--threshold")?
+                        .unwrap_or(config::get().regression_threshold),
+                    stat: args.opt_value_from_str("--stat")?.unwrap_or_default(),
+                    year: parse_year(&mut args)?,
+                    against: args.opt_free_from_str()?,
+                },
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("history") => AppArguments::History {
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
                 download: args.contains("--download"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-                dhat: args.contains("--dhat"),
+                puzzle: parse_puzzle(&mut args)?,
+                options: solve::Options {
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    record: args.contains("--record"),
+                    time: args.contains("--time"),
+                    dhat: args.contains("--dhat"),
+                    allocs: args.contains("--allocs"),
+                    stat: args.opt_value_from_str("--stat")?.unwrap_or_default(),
+                    bench: parse_bench_config(&mut args)?,
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    params: args.values_from_str("--param")?,
+                    input: parse_input_source(&mut args)?,
+                    timeout: parse_timeout(&mut args)?,
+                    inputs_dir: args.opt_value_from_str("--inputs-dir")?,
+                },
+            },
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
+            },
+            Some("progress") => AppArguments::Progress {
+                year: parse_year(&mut args)?,
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -71,6 +152,74 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Reads `--year`, defaulting to the configured year.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::configured))
+    }
+
+    /// Reads the day, which is expected as the first argument, and `--year`.
+    fn parse_puzzle(args: &mut pico_args::Arguments) -> Result<Puzzle, pico_args::Error> {
+        let day = args.free_from_str()?;
+        Ok(Puzzle::new(parse_year(args)?, day))
+    }
+
+    /// Reads `--input <path>`, `--example`, `--example-part <n>` or `--stdin`.
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Source, Box<dyn std::error::Error>> {
+        let sources = [
+            args.opt_value_from_str("--input")?.map(Source::File),
+            args.contains("--example").then_some(Source::Example),
+            args.opt_value_from_str("--example-part")?
+                .map(Source::ExamplePart),
+            args.contains("--stdin").then_some(Source::Stdin),
+        ];
+
+        let mut sources: Vec<Source> = sources.into_iter().flatten().collect();
+
+        if sources.len() > 1 {
+            return Err(
+                "only one of --input, --example, --example-part and --stdin is allowed".into(),
+            );
+        }
+
+        Ok(sources.pop().unwrap_or_default())
+    }
+
+    /// Reads `--timeout` in seconds, where `0` disables the timeout, or the configured timeout.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str::<_, u64>("--timeout")?
+            .map_or(config::get().timeout, |secs| {
+                (secs > 0).then(|| Duration::from_secs(secs))
+            }))
+    }
+
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, pico_args::Error> {
+        let default = config::get().bench;
+
+        Ok(BenchConfig {
+            warmup: args
+                .opt_value_from_str("--warmup")?
+                .map_or(default.warmup, Duration::from_millis),
+            target: args
+                .opt_value_from_str("--bench-time")?
+                .map_or(default.target, Duration::from_millis),
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(default.min_samples),
+            max_samples: args
+                .opt_value_from_str("--max-samples")?
+                .unwrap_or(default.max_samples),
+        })
+    }
 }
 
 fn main() {
@@ -80,22 +229,24 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All { options } => all::handle(&options, SOLUTIONS),
+            AppArguments::BenchDiff { options } => bench_diff::handle(&options),
+            AppArguments::Verify { year } => verify::handle(year, SOLUTIONS),
+            AppArguments::Progress { year } => progress::handle(year),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle } => examples::handle(puzzle),
+            AppArguments::History { puzzle } => history::handle(puzzle),
+            AppArguments::Scaffold { puzzle, download } => {
+                scaffold::handle(puzzle);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                dhat,
-                submit,
-            } => solve::handle(day, release, time, dhat, submit),
+            AppArguments::Solve { puzzle, options } => match &options.inputs_dir {
+                Some(dir) => cross_validate::handle(puzzle, dir, options.timeout, SOLUTIONS),
+                None => solve::handle(puzzle, &options),
+            },
         },
     };
 }
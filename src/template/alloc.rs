@@ -0,0 +1,242 @@
+/// Counts heap allocations of solutions with the `count-allocations` feature.
+///
+/// Unlike the `dhat-heap` feature, this only keeps a few counters, so it can stay enabled while
+/// benchmarking. Only the allocations of the thread that measures a stage are counted, so other
+/// threads, e.g. those of other days or the one printing reports, do not affect them.
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "count-allocations")]
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(feature = "count-allocations")]
+use std::cell::Cell;
+
+/// The allocations of a single run of a stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Allocations {
+    /// Number of allocations, including reallocations.
+    pub count: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Highest number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+impl Allocations {
+    /// Adds up the allocations of several stages. The peak is the highest of all stages.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            count: self.count + other.count,
+            bytes: self.bytes + other.bytes,
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+        }
+    }
+}
+
+impl Display for Allocations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The allocations that were counted on a thread since it started measuring.
+#[cfg(feature = "count-allocations")]
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    /// Bytes allocated minus bytes freed, which is negative if memory allocated earlier was freed.
+    live_bytes: i64,
+    peak_bytes: i64,
+}
+
+#[cfg(feature = "count-allocations")]
+thread_local! {
+    /// Whether the allocations of this thread are counted, see [`measure`].
+    static IS_MEASURING: Cell<bool> = const { Cell::new(false) };
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            count: 0,
+            bytes: 0,
+            live_bytes: 0,
+            peak_bytes: 0,
+        })
+    };
+}
+
+/// A global allocator that forwards to the system allocator and counts what passes through it.
+#[cfg(feature = "count-allocations")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+impl CountingAllocator {
+    fn on_alloc(size: usize) {
+        Self::update(|counters| {
+            let size = size as u64;
+            counters.count += 1;
+            counters.bytes += size;
+            counters.live_bytes += size as i64;
+            counters.peak_bytes = counters.peak_bytes.max(counters.live_bytes);
+        });
+    }
+
+    fn on_dealloc(size: usize) {
+        Self::update(|counters| counters.live_bytes -= size as i64);
+    }
+
+    /// Updates the counters of the current thread if it is measuring. Neither accessing the thread
+    /// locals nor updating them allocates, and they are never destroyed.
+    fn update(func: impl FnOnce(&mut Counters)) {
+        if IS_MEASURING.try_with(Cell::get).unwrap_or(false) {
+            let _ = COUNTERS.try_with(|cell| {
+                let mut counters = cell.get();
+                func(&mut counters);
+                cell.set(counters);
+            });
+        }
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::on_dealloc(layout.size());
+            Self::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `func` and returns the allocations it made, or [`None`] without `count-allocations`.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    #[cfg(not(feature = "count-allocations"))]
+    return (func(), None);
+
+    #[cfg(feature = "count-allocations")]
+    {
+        COUNTERS.set(Counters::default());
+        let was_measuring = IS_MEASURING.replace(true);
+
+        let result = func();
+
+        IS_MEASURING.set(was_measuring);
+        let counters = COUNTERS.get();
+
+        let allocations = Allocations {
+            count: counters.count,
+            bytes: counters.bytes,
+            peak_bytes: counters.peak_bytes.max(0).unsigned_abs(),
+        };
+
+        (result, Some(allocations))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, Allocations};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn merges_allocations() {
+        let a = Allocations {
+            count: 2,
+            bytes: 100,
+            peak_bytes: 80,
+        };
+        let b = Allocations {
+            count: 1,
+            bytes: 10,
+            peak_bytes: 90,
+        };
+        assert_eq!(
+            a.merge(b),
+            Allocations {
+                count: 3,
+                bytes: 110,
+                peak_bytes: 90
+            }
+        );
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn counts_allocations() {
+        let (_, allocations) = super::measure(|| vec![0_u8; 4096]);
+        let allocations = allocations.unwrap();
+        assert!(allocations.count >= 1);
+        assert!(allocations.bytes >= 4096);
+        assert!(allocations.peak_bytes >= 4096);
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn ignores_memory_allocated_before_measuring() {
+        let data = vec![0_u8; 4096];
+        let (_, allocations) = super::measure(|| drop(data));
+        assert_eq!(allocations, Some(Allocations::default()));
+    }
+}
@@ -0,0 +1,124 @@
+/// Stores the expected answers of solved days in `data/answers/`, so regressions can be detected.
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::json_store::{self, Error};
+use crate::template::Puzzle;
+
+/// The known correct answers of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: Puzzle) -> String {
+    puzzle.data_path("answers", ".json")
+}
+
+/// Loads the stored answers of a day. Returns empty [`Answers`] if none have been recorded yet.
+pub fn load(puzzle: Puzzle) -> Result<Answers, Error> {
+    load_path(Path::new(&get_path(puzzle)))
+}
+
+/// Loads answers from any file. Returns empty [`Answers`] if it does not exist.
+pub fn load_path(path: &Path) -> Result<Answers, Error> {
+    json_store::load(path)
+}
+
+pub fn save(puzzle: Puzzle, answers: &Answers) -> Result<(), Error> {
+    json_store::save(Path::new(&get_path(puzzle)), answers)
+}
+
+/// Stores `answer` as the expected answer of the given part, keeping the other part as is.
+pub fn record(puzzle: Puzzle, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = load(puzzle)?;
+    answers.set(part, answer);
+    save(puzzle, &answers)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of comparing a part's answer with the stored one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// There is no stored answer for this part.
+    Unknown,
+}
+
+pub fn verify(expected: Option<&str>, actual: Option<&str>) -> Verdict {
+    match (expected, actual) {
+        (None, _) => Verdict::Unknown,
+        (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+        (Some(expected), actual) => Verdict::Fail {
+            expected: expected.into(),
+            actual: actual.map(Into::into),
+        },
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verify, Answers, Verdict};
+
+    #[test]
+    fn serializes_recorded_parts_only() {
+        let mut answers = Answers::default();
+        answers.set(1, "35");
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"{"part_1":"35"}"#);
+        assert_eq!(serde_json::from_str::<Answers>(&json).unwrap(), answers);
+        assert_eq!(answers.get(1), Some("35"));
+        assert_eq!(answers.get(2), None);
+    }
+
+    #[test]
+    fn verifies_answers() {
+        assert_eq!(verify(Some("35"), Some("35")), Verdict::Pass);
+        assert_eq!(verify(None, Some("35")), Verdict::Unknown);
+        assert_eq!(
+            verify(Some("35"), Some("36")),
+            Verdict::Fail {
+                expected: "35".into(),
+                actual: Some("36".into())
+            }
+        );
+        assert_eq!(
+            verify(Some("35"), None),
+            Verdict::Fail {
+                expected: "35".into(),
+                actual: None
+            }
+        );
+    }
+}
@@ -0,0 +1,403 @@
+/// Minimal HTTP client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers on behalf of the user.
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::template::{config, Puzzle};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug, thiserror::Error)]
+pub enum AocClientError {
+    #[error(
+        "no session cookie found. Set AOC_SESSION or create the file \"~/.adventofcode.session\"."
+    )]
+    SessionNotFound,
+    #[error("request failed: {0}")]
+    Transport(String),
+    #[error("server responded with status {0}. Is your session cookie still valid?")]
+    BadStatus(u16),
+    #[error("could not find the puzzle description in the server response.")]
+    UnexpectedResponse,
+    #[error("could not access the file system: {0}")]
+    IO(#[from] io::Error),
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// An answer was submitted too recently, nothing was checked.
+    RateLimited,
+    /// The part has already been solved or is not unlocked yet.
+    WrongLevel,
+    /// The response could not be interpreted, contains the text of the response.
+    Unknown(String),
+}
+
+impl Verdict {
+    fn from_response(text: &str) -> Self {
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(text.trim().into())
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Verdict::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Verdict::Incorrect => write!(f, "That's not the right answer."),
+            Verdict::RateLimited => write!(f, "You gave an answer too recently, try again later."),
+            Verdict::WrongLevel => write!(f, "This part is already solved or not unlocked yet."),
+            Verdict::Unknown(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+        }
+    }
+
+    /// Creates a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, or else from the file at `AOC_SESSION_FILE`,
+    ///     the `session_file` in `aoc.toml` or `~/.adventofcode.session`.
+    ///  2. `AOC_BASE_URL` overrides the website's address, e.g. to test against a local server.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    fn day_url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn get_input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(puzzle)))
+    }
+
+    /// Returns the HTML of the puzzle description, i.e. the `<article>` elements of the puzzle page.
+    pub fn get_puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let page = self.get(&self.day_url(puzzle))?;
+        let articles = extract_articles(&page);
+
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse);
+        }
+
+        Ok(articles.join("\n\n") + "\n")
+    }
+
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let text = extract_articles(&response)
+            .iter()
+            .map(|x| html_to_text(x))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Ok(Verdict::from_response(&text))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn download(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = puzzle.data_path("inputs", ".txt");
+    let puzzle_path = puzzle.data_path("puzzles", ".md");
+
+    for path in [&input_path, &puzzle_path] {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    fs::write(&input_path, client.get_input(puzzle)?)?;
+    fs::write(&puzzle_path, client.get_puzzle(puzzle)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+pub fn read(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    println!("{}", html_to_text(&client.get_puzzle(puzzle)?));
+    Ok(())
+}
+
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<Verdict, AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, result)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let path = match (env::var("AOC_SESSION_FILE"), &config::get().session_file) {
+        (Ok(path), _) => PathBuf::from(path),
+        (Err(_), Some(path)) => path.clone(),
+        (Err(_), None) => PathBuf::from(env::var("HOME").ok()?).join(".adventofcode.session"),
+    };
+
+    fs::read_to_string(path)
+        .ok()
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn extract_articles(html: &str) -> Vec<String> {
+    let re = Regex::new(r"(?s)<article[^>]*>.*?</article>").unwrap();
+    re.find_iter(html).map(|m| m.as_str().to_string()).collect()
+}
+
+/// Strips tags and decodes the most common entities, good enough to display puzzles in a terminal.
+fn html_to_text(html: &str) -> String {
+    let blocks = Regex::new(r"</(h2|p|pre|ul)>").unwrap();
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let text = blocks.replace_all(html, "\n\n").replace("<li>", "- ");
+    let text = tags.replace_all(&text, "");
+
+    decode_entities(&text).trim().to_string()
+}
+
+/// Decodes the entities that Advent of Code uses in its puzzle descriptions.
+pub(crate) fn decode_entities(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_text, AocClient, AocClientError, Verdict};
+    use crate::day;
+    use crate::template::{Puzzle, Year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    fn get_mock_puzzle() -> Puzzle {
+        Puzzle::new(Year::new(2023).unwrap(), day!(5))
+    }
+
+    /// Serves a single request with the given status and body, returns the raw request.
+    fn serve_once(status: u16, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, server) = serve_once(200, "1 2 3\n");
+        let client = AocClient::new(&base_url, "secret\n");
+
+        assert_eq!(client.get_input(get_mock_puzzle()).unwrap(), "1 2 3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/5/input "));
+        assert!(request.contains("ookie: session=secret\r\n"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (base_url, server) = serve_once(
+            200,
+            "<main><article class=\"day-desc\"><h2>--- Day 5 ---</h2></article><p>foo</p></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        assert_eq!(
+            client.get_puzzle(get_mock_puzzle()).unwrap(),
+            "<article class=\"day-desc\"><h2>--- Day 5 ---</h2></article>\n"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, server) = serve_once(
+            200,
+            "<article><p>That's not the right answer; your answer is too high. Please wait.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        assert_eq!(
+            client.submit(get_mock_puzzle(), 2, "46").unwrap(),
+            Verdict::TooHigh
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer "));
+        assert!(request.ends_with("level=2&answer=46"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, server) = serve_once(400, "Please log in.");
+        let client = AocClient::new(&base_url, "expired");
+
+        assert!(matches!(
+            client.get_input(get_mock_puzzle()),
+            Err(AocClientError::BadStatus(400))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn interprets_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have 42s left to wait."),
+            Verdict::RateLimited
+        );
+        assert_eq!(
+            Verdict::from_response("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+    }
+
+    #[test]
+    fn converts_html_to_text() {
+        assert_eq!(
+            html_to_text("<p>Is <code>a &lt; b</code> &amp;&amp; <em>true</em>?</p>"),
+            "Is a < b && true?"
+        );
+        assert_eq!(
+            html_to_text("<h2>--- Day 1 ---</h2><p>Foo:</p><ul><li>bar</li></ul>"),
+            "--- Day 1 ---\n\nFoo:\n\n- bar"
+        );
+    }
+}
@@ -0,0 +1,289 @@
+/// Keeps the benchmarks of every `cargo time` run in `data/benchmarks.json`, so `cargo bench-diff`
+/// can tell which commit made a solution slower. The file is configured with `paths.benchmarks`.
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::json_store::{self, Error};
+use crate::template::report::{PartReport, Status};
+use crate::template::stats::{Statistic, Summary};
+use crate::template::{config, Day, Year};
+
+fn get_path() -> PathBuf {
+    let config = config::get();
+    config.resolve(&config.paths.benchmarks)
+}
+
+/// The timing of a single stage in a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: Day,
+    /// The part number, or `None` for the parse stage.
+    pub part: Option<u8>,
+    pub duration: Summary,
+}
+
+/// The benchmarks of one `cargo time` run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    pub year: u16,
+    /// The commit that was checked out, or `None` outside of a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes, i.e. the commit is not what ran.
+    pub dirty: bool,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The name given with `cargo bench-diff --save-baseline`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+    pub timings: Vec<Timing>,
+}
+
+impl Run {
+    /// A run of the current working tree, with the stages that finished successfully.
+    pub fn new(year: Year, reports: &[PartReport]) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            year: year.into_inner(),
+            commit: git(&["rev-parse", "HEAD"]),
+            dirty: git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|x| !x.is_empty()),
+            timestamp,
+            baseline: None,
+            timings: reports
+                .iter()
+                .filter(|x| x.status == Status::Ok)
+                .map(|x| Timing {
+                    day: x.day,
+                    part: x.part,
+                    duration: x.duration,
+                })
+                .collect(),
+        }
+    }
+
+    /// The abbreviated commit, e.g. `6b2b0d7` or `6b2b0d7-dirty`.
+    pub fn short_commit(&self) -> String {
+        let commit = self
+            .commit
+            .as_deref()
+            .map_or("unknown", |x| &x[..x.len().min(7)]);
+        if self.dirty {
+            format!("{commit}-dirty")
+        } else {
+            commit.into()
+        }
+    }
+
+    fn timing(&self, day: Day, part: Option<u8>) -> Option<&Timing> {
+        self.timings.iter().find(|x| x.day == day && x.part == part)
+    }
+}
+
+/// Runs git in the project root and returns its trimmed output, or `None` if it failed.
+fn git(args: &[&str]) -> Option<String> {
+    let root = &config::get().root;
+    let dir = if root.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        root.clone()
+    };
+
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Loads all runs, oldest first. Returns no runs if none were recorded yet.
+pub fn load() -> Result<Vec<Run>, Error> {
+    json_store::load(&get_path())
+}
+
+pub fn save(runs: &[Run]) -> Result<(), Error> {
+    json_store::save(&get_path(), runs)
+}
+
+/// Appends a run to the history.
+pub fn record(run: Run) -> Result<(), Error> {
+    let mut runs = load()?;
+    runs.push(run);
+    save(&runs)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds the run to compare the latest run of `year` against, given as an index into `runs`.
+///
+/// Without `against`, this is the run before the latest one. Otherwise it is the latest run that
+/// was saved as the baseline of that name, or else the latest run of a commit that starts with
+/// `against`, e.g. `6b2b0d7` or the full hash of a resolved git revision.
+pub fn find_reference(runs: &[Run], year: u16, against: Option<&str>) -> Option<usize> {
+    let mut candidates = runs
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, run)| run.year == year)
+        .skip(1);
+
+    let Some(against) = against else {
+        return candidates.next().map(|(i, _)| i);
+    };
+
+    let candidates: Vec<(usize, &Run)> = candidates.collect();
+
+    candidates
+        .iter()
+        .find(|(_, run)| run.baseline.as_deref() == Some(against))
+        .or_else(|| {
+            candidates.iter().find(|(_, run)| {
+                run.commit
+                    .as_deref()
+                    .is_some_and(|commit| !against.is_empty() && commit.starts_with(against))
+            })
+        })
+        .map(|(i, _)| *i)
+}
+
+/// Resolves a git revision like `HEAD~1` or a branch name to the full hash of its commit.
+pub fn resolve_revision(revision: &str) -> Option<String> {
+    git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{revision}^{{commit}}"),
+    ])
+}
+
+/// How the timing of a stage changed between two runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    pub part: Option<u8>,
+    pub before: Duration,
+    pub after: Duration,
+    /// Whether the stage got slower by more than the threshold.
+    pub is_regression: bool,
+}
+
+impl Change {
+    pub fn label(&self) -> String {
+        match self.part {
+            Some(part) => format!("Part {part}"),
+            None => "Parse".into(),
+        }
+    }
+
+    /// The relative change in percent, e.g. `100.0` if the stage takes twice as long.
+    pub fn percent(&self) -> f64 {
+        let before = self.before.as_secs_f64();
+        if before == 0.0 {
+            return 0.0;
+        }
+        (self.after.as_secs_f64() / before - 1.0) * 100.0
+    }
+}
+
+/// Compares the stages that ran in both runs. `threshold` is the slowdown in percent above which
+/// a stage is considered a regression.
+pub fn compare(before: &Run, after: &Run, statistic: Statistic, threshold: f64) -> Vec<Change> {
+    let mut changes: Vec<Change> = after
+        .timings
+        .iter()
+        .filter_map(|timing| {
+            let previous = before.timing(timing.day, timing.part)?;
+
+            let mut change = Change {
+                day: timing.day,
+                part: timing.part,
+                before: previous.duration.get(statistic),
+                after: timing.duration.get(statistic),
+                is_regression: false,
+            };
+
+            change.is_regression = change.percent() > threshold;
+            Some(change)
+        })
+        .collect();
+
+    changes.sort_by_key(|x| (x.day, x.part));
+    changes
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, find_reference, Run, Timing};
+    use crate::day;
+    use crate::template::stats::{Statistic, Summary};
+    use std::time::Duration;
+
+    fn get_mock_run(commit: &str, timings: &[(u8, Option<u8>, u64)]) -> Run {
+        Run {
+            year: 2023,
+            commit: Some(commit.into()),
+            dirty: false,
+            timestamp: 0,
+            baseline: None,
+            timings: timings
+                .iter()
+                .map(|&(day, part, nanos)| Timing {
+                    day: crate::template::Day::new(day).unwrap(),
+                    part,
+                    duration: Summary {
+                        mean: Duration::from_nanos(nanos),
+                        ..Summary::default()
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let before = get_mock_run(
+            "a",
+            &[(5, Some(1), 1000), (5, Some(2), 1000), (6, None, 10)],
+        );
+        let after = get_mock_run(
+            "b",
+            &[(5, Some(1), 2000), (5, Some(2), 1050), (7, None, 10)],
+        );
+
+        let changes = compare(&before, &after, Statistic::Mean, 10.0);
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].day, day!(5));
+        assert_eq!(changes[0].percent(), 100.0);
+        assert!(changes[0].is_regression);
+        assert!(!changes[1].is_regression);
+    }
+
+    #[test]
+    fn finds_reference_runs() {
+        let mut runs = vec![
+            get_mock_run("1111111", &[]),
+            get_mock_run("2222222", &[]),
+            get_mock_run("3333333", &[]),
+        ];
+        runs[0].baseline = Some("main".into());
+
+        assert_eq!(find_reference(&runs, 2023, None), Some(1));
+        assert_eq!(find_reference(&runs, 2023, Some("main")), Some(0));
+        assert_eq!(find_reference(&runs, 2023, Some("2222")), Some(1));
+        assert_eq!(find_reference(&runs, 2023, Some("3333")), None);
+        assert_eq!(find_reference(&runs, 2022, None), None);
+    }
+}
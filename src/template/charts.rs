@@ -0,0 +1,436 @@
+/// Renders the benchmarks of `cargo time` as self-contained SVG charts in `.assets/`, which are
+/// embedded into the readme next to the benchmark table.
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::bench_history::{self, Run};
+use crate::template::dates::format_timestamp;
+use crate::template::json_store;
+use crate::template::stats::{format_duration, Statistic};
+use crate::template::{config, Day, Year};
+
+/// The folder next to the readme that the charts are written to.
+pub const ASSETS_FOLDER: &str = ".assets";
+
+const BARS_FILE: &str = "benchmarks.svg";
+const TREND_FILE: &str = "benchmarks-trend.svg";
+
+/// The most recent runs that the trend chart shows.
+const MAX_TREND_RUNS: usize = 30;
+
+const WIDTH_PER_DAY: u32 = 36;
+const HEIGHT: u32 = 280;
+const MARGIN_LEFT: u32 = 64;
+const MARGIN_RIGHT: u32 = 16;
+const MARGIN_TOP: u32 = 40;
+const MARGIN_BOTTOM: u32 = 40;
+
+const COLOR_PART_1: &str = "#4e79a7";
+const COLOR_PART_2: &str = "#f28e2b";
+const COLOR_GRID: &str = "#d0d7de";
+const COLOR_TEXT: &str = "#57606a";
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    History(json_store::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<json_store::Error> for Error {
+    fn from(e: json_store::Error) -> Self {
+        Error::History(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "could not write chart: {e}"),
+            Error::History(e) => e.fmt(f),
+        }
+    }
+}
+
+/// A chart that was written to the assets folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub alt: String,
+    /// The path relative to the readme, e.g. `./.assets/benchmarks.svg`.
+    pub path: String,
+}
+
+/// Renders the charts of `year` from the benchmark history. Returns the images to embed into the
+/// readme, which is empty if there is no run of `year` yet.
+pub fn write(year: Year, statistic: Statistic) -> Result<Vec<Image>, Error> {
+    let runs: Vec<Run> = bench_history::load()?
+        .into_iter()
+        .filter(|x| x.year == year.into_inner())
+        .collect();
+
+    let Some(latest) = runs.last() else {
+        return Ok(vec![]);
+    };
+
+    let config = config::get();
+    let readme = config.resolve(&config.readme.path);
+    let folder = readme
+        .parent()
+        .map_or_else(|| PathBuf::from(ASSETS_FOLDER), |x| x.join(ASSETS_FOLDER));
+    fs::create_dir_all(&folder)?;
+
+    let charts = [
+        (
+            "Benchmarks per day",
+            BARS_FILE,
+            Some(bar_chart(latest, statistic)),
+        ),
+        (
+            "Benchmark history",
+            TREND_FILE,
+            trend_chart(&runs, statistic),
+        ),
+    ];
+
+    let mut images = vec![];
+
+    for (alt, file, svg) in charts {
+        let Some(svg) = svg else { continue };
+        fs::write(folder.join(file), svg)?;
+        images.push(Image {
+            alt: alt.into(),
+            path: format!("./{ASSETS_FOLDER}/{file}"),
+        });
+    }
+
+    Ok(images)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A bar chart of the part 1 and part 2 times of every day in a run, on a logarithmic scale.
+pub fn bar_chart(run: &Run, statistic: Statistic) -> String {
+    let days: BTreeSet<Day> = run
+        .timings
+        .iter()
+        .filter(|x| x.part.is_some())
+        .map(|x| x.day)
+        .collect();
+
+    let nanos = |day: Day, part: u8| {
+        run.timings
+            .iter()
+            .find(|x| x.day == day && x.part == Some(part))
+            .map(|x| to_nanos(x.duration.get(statistic)))
+    };
+
+    let width =
+        MARGIN_LEFT + MARGIN_RIGHT + WIDTH_PER_DAY * u32::try_from(days.len().max(1)).unwrap_or(25);
+    let plot_bottom = f64::from(HEIGHT - MARGIN_BOTTOM);
+    let plot_height = f64::from(HEIGHT - MARGIN_TOP - MARGIN_BOTTOM);
+
+    let values: Vec<f64> = days
+        .iter()
+        .flat_map(|&day| [nanos(day, 1), nanos(day, 2)])
+        .flatten()
+        .collect();
+
+    // the axis spans whole decades, so every gridline is a round number like 10µs.
+    let min = values
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min)
+        .max(1.0);
+    let max = values.iter().copied().fold(0.0, f64::max).max(1.0);
+    let lo = min.log10().floor();
+    let hi = max.log10().ceil().max(lo + 1.0);
+    let y = |nanos: f64| plot_bottom - (nanos.max(1.0).log10() - lo) / (hi - lo) * plot_height;
+
+    let mut svg = header(width, &format!("Benchmarks per day ({statistic})"));
+
+    #[allow(clippy::cast_possible_truncation)]
+    for exponent in (lo as i32)..=(hi as i32) {
+        let nanos = 10_f64.powi(exponent);
+        gridline(
+            &mut svg,
+            width,
+            y(nanos),
+            &format_duration(from_nanos(nanos)),
+        );
+    }
+
+    legend(&mut svg, width);
+
+    for (i, &day) in days.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = f64::from(MARGIN_LEFT) + (i as f64 + 0.5) * f64::from(WIDTH_PER_DAY);
+
+        for (part, color, offset) in [(1, COLOR_PART_1, -13.0), (2, COLOR_PART_2, 1.0)] {
+            let Some(value) = nanos(day, part) else {
+                continue;
+            };
+            let top = y(value);
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{:.1}" y="{top:.1}" width="12" height="{:.1}" fill="{color}"><title>Day {day} part {part}: {}</title></rect>"#,
+                x + offset,
+                (plot_bottom - top).max(1.0),
+                format_duration(from_nanos(value))
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{x:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            plot_bottom + 16.0,
+            day.into_inner()
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{:.1}" y="{}" text-anchor="middle">Day</text>"#,
+        f64::from(width) / 2.0,
+        HEIGHT - 6
+    );
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A line chart of the total time of the runs in the history, or `None` if there are less than
+/// two comparable runs.
+///
+/// The total only grows when days are added, so a run is left out if it ran fewer days than the
+/// run before it, e.g. when only a single day was timed.
+pub fn trend_chart(runs: &[Run], statistic: Statistic) -> Option<String> {
+    let mut points: Vec<(&Run, f64)> = vec![];
+    let mut previous_days: BTreeSet<Day> = BTreeSet::new();
+
+    for run in runs {
+        let days: BTreeSet<Day> = run.timings.iter().map(|x| x.day).collect();
+        if days.is_empty() || !days.is_superset(&previous_days) {
+            continue;
+        }
+
+        let total = run
+            .timings
+            .iter()
+            .map(|x| to_nanos(x.duration.get(statistic)))
+            .sum();
+
+        points.push((run, total));
+        previous_days = days;
+    }
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let points = &points[points.len().saturating_sub(MAX_TREND_RUNS)..];
+
+    let width = MARGIN_LEFT + MARGIN_RIGHT + WIDTH_PER_DAY * 16;
+    let plot_bottom = f64::from(HEIGHT - MARGIN_BOTTOM);
+    let plot_height = f64::from(HEIGHT - MARGIN_TOP - MARGIN_BOTTOM);
+    let plot_width = f64::from(width - MARGIN_LEFT - MARGIN_RIGHT);
+
+    // gridlines are spaced by a round step like 20µs, with four or fewer above zero.
+    let highest = points.iter().map(|x| x.1).fold(0.0, f64::max).max(1.0);
+    let step = round_step(highest / 4.0);
+    let ticks = (highest / step).ceil().max(1.0);
+    let max = ticks * step;
+    let y = |nanos: f64| plot_bottom - nanos / max * plot_height;
+    #[allow(clippy::cast_precision_loss)]
+    let x = |i: usize| f64::from(MARGIN_LEFT) + i as f64 / (points.len() - 1) as f64 * plot_width;
+
+    let mut svg = header(width, &format!("Total time of all days ({statistic})"));
+
+    #[allow(clippy::cast_possible_truncation)]
+    for i in 0..=(ticks as i32) {
+        let nanos = step * f64::from(i);
+        gridline(
+            &mut svg,
+            width,
+            y(nanos),
+            &format_duration(from_nanos(nanos)),
+        );
+    }
+
+    let line: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(i, (_, total))| format!("{:.1},{:.1}", x(i), y(*total)))
+        .collect();
+
+    let _ = writeln!(
+        svg,
+        r#"  <polyline points="{}" fill="none" stroke="{COLOR_PART_1}" stroke-width="2"/>"#,
+        line.join(" ")
+    );
+
+    // labels are thinned out, so they do not overlap.
+    let label_every = points.len().div_ceil(8);
+
+    for (i, (run, total)) in points.iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            r#"  <circle cx="{:.1}" cy="{:.1}" r="3" fill="{COLOR_PART_1}"><title>{} · {} · {}</title></circle>"#,
+            x(i),
+            y(*total),
+            run.short_commit(),
+            format_timestamp(run.timestamp),
+            format_duration(from_nanos(*total))
+        );
+
+        if i % label_every == 0 || i == points.len() - 1 {
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+                x(i),
+                plot_bottom + 16.0,
+                run.short_commit()
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    Some(svg)
+}
+
+fn header(width: u32, title: &str) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{HEIGHT}" viewBox="0 0 {width} {HEIGHT}" font-family="-apple-system, Segoe UI, Helvetica, Arial, sans-serif" font-size="11" fill="{COLOR_TEXT}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <rect width="{width}" height="{HEIGHT}" fill="white"/>"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{MARGIN_LEFT}" y="20" font-size="14" font-weight="bold">{title}</text>"#
+    );
+    svg
+}
+
+fn gridline(svg: &mut String, width: u32, y: f64, label: &str) {
+    let _ = writeln!(
+        svg,
+        r#"  <line x1="{MARGIN_LEFT}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="{COLOR_GRID}"/>"#,
+        width - MARGIN_RIGHT
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{}" y="{:.1}" text-anchor="end">{label}</text>"#,
+        MARGIN_LEFT - 6,
+        y + 4.0
+    );
+}
+
+fn legend(svg: &mut String, width: u32) {
+    for (i, (label, color)) in [("Part 1", COLOR_PART_1), ("Part 2", COLOR_PART_2)]
+        .into_iter()
+        .enumerate()
+    {
+        let x = width - MARGIN_RIGHT - 120 + 60 * u32::try_from(i).unwrap_or(0);
+        let _ = writeln!(
+            svg,
+            r#"  <rect x="{x}" y="11" width="10" height="10" fill="{color}"/><text x="{}" y="20">{label}</text>"#,
+            x + 14
+        );
+    }
+}
+
+/// Rounds up to 1, 2 or 5 times a power of ten.
+fn round_step(value: f64) -> f64 {
+    let magnitude = 10_f64.powf(value.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .find(|x| x * magnitude >= value)
+        .unwrap_or(10.0);
+    step * magnitude
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_nanos(duration: Duration) -> f64 {
+    duration.as_nanos() as f64
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bar_chart, round_step, trend_chart};
+    use crate::template::bench_history::{Run, Timing};
+    use crate::template::stats::{Statistic, Summary};
+    use crate::template::Day;
+    use std::time::Duration;
+
+    fn get_mock_run(commit: &str, timings: &[(u8, u8, u64)]) -> Run {
+        Run {
+            year: 2023,
+            commit: Some(commit.into()),
+            dirty: false,
+            timestamp: 1_701_406_812,
+            baseline: None,
+            timings: timings
+                .iter()
+                .map(|&(day, part, nanos)| Timing {
+                    day: Day::new(day).unwrap(),
+                    part: Some(part),
+                    duration: Summary {
+                        mean: Duration::from_nanos(nanos),
+                        ..Summary::default()
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn rounds_gridline_steps() {
+        assert_eq!(round_step(45_000.0), 50_000.0);
+        assert_eq!(round_step(1_200.0), 2_000.0);
+        assert_eq!(round_step(100.0), 100.0);
+    }
+
+    #[test]
+    fn renders_bars_on_log_scale() {
+        let run = get_mock_run("a", &[(1, 1, 1_500), (1, 2, 2_000_000), (2, 1, 40_000)]);
+        let svg = bar_chart(&run, Statistic::Mean);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>Day").count(), 3);
+        for label in [">1.00µs<", ">10.0µs<", ">100µs<", ">1.00ms<", ">10.0ms<"] {
+            assert!(svg.contains(label), "missing gridline {label}");
+        }
+    }
+
+    #[test]
+    fn skips_partial_runs_in_trend() {
+        let full = get_mock_run("1111111", &[(1, 1, 100), (2, 1, 100)]);
+        let partial = get_mock_run("2222222", &[(2, 1, 100)]);
+        let next = get_mock_run("3333333", &[(1, 1, 100), (2, 1, 300), (3, 1, 100)]);
+
+        assert_eq!(trend_chart(&[full.clone()], Statistic::Mean), None);
+
+        let svg = trend_chart(&[full, partial, next], Statistic::Mean).unwrap();
+        assert_eq!(svg.matches("<circle").count(), 2);
+        assert!(!svg.contains("2222222"));
+    }
+}
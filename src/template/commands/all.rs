@@ -1,254 +1,498 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::process::{self, Command};
+use std::sync::mpsc;
+use std::time::Duration;
+use std::{env, thread};
+
+use rayon::{prelude::*, ThreadPoolBuilder};
 
 use crate::template::{
-    all_days,
-    readme_benchmarks::{self, Timings},
-    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    answers::{self, Answers},
+    bench_history, charts,
+    fingerprints::{self, Fingerprints},
+    input,
+    readme_benchmarks::{self, Outcome, Timings},
+    registry::{self, Entry},
+    report::{Format, PartReport, Status},
+    runner::{print_report, BenchConfig, RunOptions},
+    stats::Statistic,
+    Day, DaySet, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Flags of the `all` command.
+pub struct Options {
+    /// Write the benchmarks to the readme. Only meaningful for optimized builds.
+    pub release: bool,
+    pub time: bool,
+    pub stat: Statistic,
+    pub bench: BenchConfig,
+    pub format: Format,
+    /// Run days on this many threads instead of sequentially.
+    pub jobs: Option<usize>,
+    pub year: Year,
+    pub days: DaySet,
+    /// Only run days that have stored answers.
+    pub solved_only: bool,
+    /// Only run days whose source changed since their last run.
+    pub changed: bool,
+    /// Report a stage as failed if it runs for longer than this.
+    pub timeout: Option<Duration>,
+    /// Count the allocations of every stage, see [`alloc`](crate::template::alloc).
+    pub allocs: bool,
+}
+
+pub fn handle(options: &Options, solutions: &[Entry]) {
+    let Options {
+        release: is_release,
+        time: is_timed,
+        stat: statistic,
+        ref bench,
+        format,
+        jobs,
+        timeout,
+        ..
+    } = *options;
+
+    if options.allocs && !cfg!(feature = "count-allocations") {
+        rerun_with_allocations();
+    }
+
+    let puzzles = select_puzzles(options);
+
+    if jobs.is_some() && is_timed {
+        eprintln!(
+            "Warning: running days in parallel skews their timings. Drop --jobs for benchmarks."
+        );
+    }
+
+    let mut run_options = RunOptions {
+        format,
+        statistic,
+        bench: is_timed.then_some(*bench),
+        progress: format == Format::Human,
+        timeout,
+    };
+
     let mut timings: Vec<Timings> = vec![];
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+    // days that ran without failures, `--changed` retries the others.
+    let mut passed_puzzles: Vec<Puzzle> = vec![];
+    let mut failures: Vec<(Puzzle, PartReport)> = vec![];
+    let mut timed_reports: Vec<PartReport> = vec![];
+
+    let mut finish_day = |puzzle: Puzzle, reports: &[PartReport]| {
+        failures.extend(
+            reports
+                .iter()
+                .filter(|x| x.status == Status::Failed)
+                .map(|x| (puzzle, x.clone())),
+        );
+
+        if reports.is_empty() {
+            if format == Format::Human {
+                println!("Not solved.");
+            }
+            // days that were started get a row in the readme, the others are not listed.
+            if registry::find(solutions, puzzle).is_some() {
+                timings.push(Timings::unsolved(puzzle.day));
+            }
+        } else {
+            timings.push(get_timings(reports, puzzle.day, statistic));
+            timed_reports.extend(reports.iter().cloned());
+            if reports.iter().all(|x| x.status != Status::Failed) {
+                passed_puzzles.push(puzzle);
+            }
         }
+    };
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    match jobs {
+        None => puzzles.iter().enumerate().for_each(|(i, &puzzle)| {
+            print_header(solutions, puzzle, i == 0, format);
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let reports = run_day(solutions, puzzle, &run_options, &mut |report| {
+                print_report(report, format, statistic);
+            });
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            finish_day(puzzle, &reports);
+        }),
+        Some(jobs) => {
+            // output is buffered per day, progress would interleave.
+            run_options.progress = false;
+
+            let mut is_first = true;
+
+            run_parallel(
+                solutions,
+                &puzzles,
+                jobs,
+                &run_options,
+                |puzzle, reports| {
+                    print_header(solutions, puzzle, is_first, format);
+                    is_first = false;
+
+                    for report in &reports {
+                        print_report(report, format, statistic);
+                    }
+
+                    finish_day(puzzle, &reports);
+                },
+            );
         }
-    });
+    }
+
+    if let Err(e) = fingerprints::record(&passed_puzzles) {
+        eprintln!("Failed to record fingerprints: {e}");
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if format == Format::Human {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+
+        if is_release && !timed_reports.is_empty() {
+            let run = bench_history::Run::new(options.year, &timed_reports);
+            if let Err(e) = bench_history::record(run) {
+                eprintln!("Failed to record benchmarks: {e}");
+            }
+        }
+
+        if is_release && options.year != Year::configured() {
+            eprintln!(
+                "Skipped updating the readme with benchmarks, since it tracks the configured year."
+            );
+        } else if is_release && puzzles.len() < 25 {
+            eprintln!("Skipped updating the readme with benchmarks, since not all days were run.");
+        } else if is_release {
+            let images = charts::write(options.year, statistic).unwrap_or_else(|e| {
+                eprintln!("Failed to render benchmark charts: {e}");
+                vec![]
+            });
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+            match readme_benchmarks::update(timings, total_millis, statistic, &images) {
+                Ok(()) => {
+                    if format == Format::Human {
+                        println!("Successfully updated README with benchmarks.");
+                    }
+                }
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
         }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
-}
+    if !failures.is_empty() {
+        if format == Format::Human {
+            println!(
+                "\n{ANSI_BOLD}{} stage(s) failed:{ANSI_RESET}",
+                failures.len()
+            );
+            for (puzzle, report) in &failures {
+                let error = report.error.as_deref().unwrap_or_default();
+                println!("Day {} {}: {error}", puzzle.day, report.label());
+            }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+            let abandoned = registry::abandoned_threads();
+            if abandoned > 0 {
+                println!(
+                    "{abandoned} timed out stage(s) kept running in the background, which may have slowed down the days after them."
+                );
+            }
+        }
+        process::exit(1);
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Runs the current command again in a build with the `count-allocations` feature, since the
+/// solutions are compiled into this binary. The build uses the profile of the running binary, so
+/// the allocations match those of its timed runs. Exits with the status of that run.
+fn rerun_with_allocations() -> ! {
+    let mut cmd_args = vec!["run", "--quiet", "--features", "count-allocations"];
+    if !cfg!(debug_assertions) {
+        cmd_args.push("--release");
+    }
+
+    let status = Command::new("cargo")
+        .args(cmd_args)
+        .arg("--")
+        .args(env::args().skip(1).filter(|x| x != "--allocs"))
+        .status();
+
+    match status {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to rerun with allocation counting: {e}");
+            process::exit(1);
+        }
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+/// The puzzles that were selected via the arguments of the command.
+fn select_puzzles(options: &Options) -> Vec<Puzzle> {
+    let year = options.year;
+    let mut days = options.days.clone();
+
+    if options.solved_only {
+        days.retain(|&day| match answers::load(Puzzle::new(year, day)) {
+            Ok(answers) => answers != Answers::default(),
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                false
+            }
+        });
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    if options.changed {
+        let fingerprints = fingerprints::load().unwrap_or_else(|e| {
+            eprintln!("Failed to load fingerprints, running all selected days: {e}");
+            Fingerprints::new()
+        });
+        days.retain(|&day| fingerprints::has_changed(&fingerprints, Puzzle::new(year, day)));
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    days.iter().map(|day| Puzzle::new(year, day)).collect()
+}
 
-        if is_release {
-            args.push("--release");
+fn print_header(solutions: &[Entry], puzzle: Puzzle, is_first: bool, format: Format) {
+    if format == Format::Human {
+        if !is_first {
+            println!();
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let header = match registry::find(solutions, puzzle).and_then(Entry::title) {
+            Some(title) => format!("Day {}: {title}", puzzle.day),
+            None => format!("Day {}", puzzle.day),
+        };
+
+        println!("{ANSI_BOLD}{header}{ANSI_RESET}");
+        println!("{}", "-".repeat(header.chars().count()));
+    }
+}
+
+/// Runs `puzzles` on a pool of `jobs` threads, or one thread per CPU if `jobs` is 0.
+/// `on_day` is called in order, as soon as a puzzle and all puzzles before it have finished.
+fn run_parallel(
+    solutions: &[Entry],
+    puzzles: &[Puzzle],
+    jobs: usize,
+    options: &RunOptions,
+    mut on_day: impl FnMut(Puzzle, Vec<PartReport>),
+) {
+    let pool = match ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Failed to create thread pool: {e}");
+            process::exit(1);
         }
+    };
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    let (tx, rx) = mpsc::channel();
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            pool.install(|| {
+                puzzles.par_iter().for_each_with(tx, |tx, &puzzle| {
+                    let reports = run_day(solutions, puzzle, options, &mut |_| {});
+                    // the receiver only hangs up if the main thread panicked.
+                    let _ = tx.send((puzzle, reports));
+                });
+            });
+        });
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let mut pending: BTreeMap<Puzzle, Vec<PartReport>> = BTreeMap::new();
+        let mut next = puzzles.iter().peekable();
 
-        let mut output = vec![];
+        for (puzzle, reports) in rx {
+            pending.insert(puzzle, reports);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            while let Some(reports) = next.peek().and_then(|x| pending.remove(x)) {
+                on_day(*next.next().unwrap(), reports);
+            }
+        }
+    });
+}
+
+/// Collects the timings of a day from the reports of its solution.
+fn get_timings(reports: &[PartReport], day: Day, statistic: Statistic) -> Timings {
+    let mut timings = Timings {
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+        allocations: None,
+    };
+
+    for report in reports {
+        let outcome = Some(match report.status {
+            Status::Ok => Outcome::Timed {
+                duration: report.duration,
+                samples: report.samples,
+            },
+            Status::Unsolved => Outcome::Unsolved,
+            Status::Failed => Outcome::Failed,
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        match report.part {
+            None => timings.parse = outcome,
+            Some(1) => timings.part_1 = outcome,
+            Some(2) => timings.part_2 = outcome,
+            Some(_) => continue,
+        }
+
+        if report.status != Status::Ok {
+            continue;
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        if let Some(allocations) = report.allocations {
+            timings.allocations = Some(timings.allocations.unwrap_or_default().merge(allocations));
+        }
 
-        Ok(output)
+        #[allow(clippy::cast_precision_loss)]
+        {
+            timings.total_nanos += report.duration.get(statistic).as_nanos() as f64;
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    timings
+}
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+/// Runs the registered solution of a puzzle in-process on its input.
+/// `on_report` is called for every stage as soon as it has finished.
+/// Returns no reports if the puzzle has no solution or its input could not be read.
+pub(crate) fn run_day(
+    solutions: &[Entry],
+    puzzle: Puzzle,
+    options: &RunOptions,
+    on_report: &mut dyn FnMut(&PartReport),
+) -> Vec<PartReport> {
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        return vec![];
+    };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    let input = match input::read_input(puzzle) {
+        Ok(input) => input,
+        Err(e @ input::Error::IO(..)) => {
+            eprintln!("Could not read input of day {}: {e}", puzzle.day);
+            return vec![];
+        }
+        Err(e) => {
+            eprintln!(
+                "Could not read input of day {}: {e}. Run `{}` to download it.",
+                puzzle.day,
+                input::download_command(puzzle)
+            );
+            return vec![];
+        }
+    };
 
-                timings.total_nanos += nanos;
-            });
+    let mut reports = vec![];
+    solution.run_isolated(input, options, &mut |report| {
+        on_report(report);
+        reports.push(report.clone());
+    });
+    reports
+}
 
-        timings
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::get_timings;
+    use crate::day;
+    use crate::template::{
+        readme_benchmarks::Outcome,
+        report::PartReport,
+        stats::{Statistic, Stats},
+        Puzzle, Year,
+    };
+    use std::time::Duration;
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// The duration of a timed stage in the given statistic.
+    fn get_duration(outcome: Option<Outcome>, statistic: Statistic) -> Option<Duration> {
+        match outcome? {
+            Outcome::Timed { duration, .. } => Some(duration.get(statistic)),
+            _ => None,
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn get_mock_report(part: Option<u8>, answer: Option<&str>, nanos: &[u64]) -> PartReport {
+        let stats = Stats::new(nanos.iter().copied().map(Duration::from_nanos).collect());
+        let puzzle = Puzzle::new(Year::new(2023).unwrap(), day!(1));
+        PartReport::new(puzzle, part, answer.map(Into::into), &stats)
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn test_well_formed() {
+        let res = get_timings(
+            &[
+                get_mock_report(Some(1), Some("0"), &[74, 74]),
+                get_mock_report(Some(2), Some("10"), &[74_130_000, 74_130_000]),
+            ],
+            day!(1),
+            Statistic::Mean,
+        );
+        assert_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.parse, None);
+        assert_eq!(
+            get_duration(res.part_1, Statistic::Mean),
+            Some(Duration::from_nanos(74))
+        );
+        assert_eq!(
+            get_duration(res.part_2, Statistic::Mean),
+            Some(Duration::from_nanos(74_130_000))
+        );
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
+    #[test]
+    fn test_statistic() {
+        let res = get_timings(
+            &[get_mock_report(Some(1), Some("0"), &[10, 20, 90])],
+            day!(1),
+            Statistic::Median,
+        );
+        assert_eq!(res.total_nanos, 20_f64);
+        assert_eq!(
+            get_duration(res.part_1, Statistic::Median),
+            Some(Duration::from_nanos(20))
+        );
+    }
 
-        use crate::day;
+    #[test]
+    fn test_parse_stage() {
+        let res = get_timings(
+            &[
+                get_mock_report(None, None, &[1500]),
+                get_mock_report(Some(1), Some("0"), &[100]),
+            ],
+            day!(1),
+            Statistic::Mean,
+        );
+        assert_eq!(res.total_nanos, 1600_f64);
+        assert_eq!(
+            get_duration(res.parse, Statistic::Mean),
+            Some(Duration::from_nanos(1500))
+        );
+        assert_eq!(
+            get_duration(res.part_1, Statistic::Mean),
+            Some(Duration::from_nanos(100))
+        );
+    }
 
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn test_missing_parts() {
+        let res = get_timings(
+            &[
+                get_mock_report(Some(1), None, &[100]),
+                get_mock_report(Some(2), None, &[100]),
+            ],
+            day!(1),
+            Statistic::Mean,
+        );
+        assert_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1, Some(Outcome::Unsolved));
+        assert_eq!(res.part_2, Some(Outcome::Unsolved));
     }
 }
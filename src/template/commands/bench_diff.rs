@@ -0,0 +1,127 @@
+use std::process;
+
+use crate::template::{
+    bench_history::{self, Run},
+    dates::format_timestamp,
+    stats::{format_duration, Statistic},
+    Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Flags of the `bench-diff` command.
+pub struct Options {
+    /// A baseline name, commit hash or git revision to compare against, else the previous run.
+    pub against: Option<String>,
+    /// Give the latest run this name instead of comparing it.
+    pub save_baseline: Option<String>,
+    /// In percent.
+    pub threshold: u32,
+    pub stat: Statistic,
+    pub year: Year,
+}
+
+/// Compares the latest `cargo time` run with an earlier one and flags the stages that got slower.
+/// Exits with a non-zero status if any stage got slower by more than the threshold.
+pub fn handle(options: &Options) {
+    let mut runs = bench_history::load().unwrap_or_else(|e| {
+        eprintln!("Failed to load benchmark history: {e}");
+        process::exit(1);
+    });
+
+    let year = options.year.into_inner();
+
+    let Some(latest) = runs.iter().rposition(|x| x.year == year) else {
+        eprintln!("No benchmarks of {year} recorded yet, run `cargo time` first.");
+        process::exit(1);
+    };
+
+    if let Some(name) = &options.save_baseline {
+        save_baseline(&mut runs, latest, name);
+        return;
+    }
+
+    let against = options.against.as_deref();
+
+    let reference = bench_history::find_reference(&runs, year, against).or_else(|| {
+        let commit = bench_history::resolve_revision(against?)?;
+        bench_history::find_reference(&runs, year, Some(&commit))
+    });
+
+    let Some(reference) = reference else {
+        match against {
+            Some(against) => eprintln!("No earlier run matches `{against}`."),
+            None => eprintln!("Only one run of {year} recorded, nothing to compare with."),
+        }
+        process::exit(1);
+    };
+
+    let (before, after) = (&runs[reference], &runs[latest]);
+    let changes = bench_history::compare(before, after, options.stat, f64::from(options.threshold));
+
+    println!(
+        "Comparing {} with {} ({}, threshold {}%):\n",
+        describe(after),
+        describe(before),
+        options.stat,
+        options.threshold
+    );
+
+    if changes.is_empty() {
+        println!("The runs have no stages in common.");
+        return;
+    }
+
+    for change in &changes {
+        let line = format!(
+            "Day {} {:<6} {:>10} → {:<10} {:>+8.1}%",
+            change.day,
+            change.label(),
+            format_duration(change.before),
+            format_duration(change.after),
+            change.percent()
+        );
+
+        if change.is_regression {
+            println!("{ANSI_BOLD}{line}  slower{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    let regressions = changes.iter().filter(|x| x.is_regression).count();
+
+    println!();
+    if regressions > 0 {
+        println!(
+            "{ANSI_BOLD}{regressions} stage(s) got slower by more than {}%.{ANSI_RESET}",
+            options.threshold
+        );
+        process::exit(1);
+    }
+    println!("No stage got slower by more than {}%.", options.threshold);
+}
+
+/// Names the run at `index`, taking the name from any earlier run.
+fn save_baseline(runs: &mut [Run], index: usize, name: &str) {
+    for run in runs.iter_mut() {
+        if run.baseline.as_deref() == Some(name) {
+            run.baseline = None;
+        }
+    }
+    runs[index].baseline = Some(name.into());
+
+    if let Err(e) = bench_history::save(runs) {
+        eprintln!("Failed to save benchmark history: {e}");
+        process::exit(1);
+    }
+
+    println!("Saved {} as baseline `{name}`.", describe(&runs[index]));
+}
+
+/// Describes a run by its commit and time, e.g. `6b2b0d7 (2023-12-05 10:00:00)`.
+fn describe(run: &Run) -> String {
+    format!(
+        "{} ({})",
+        run.short_commit(),
+        format_timestamp(run.timestamp)
+    )
+}
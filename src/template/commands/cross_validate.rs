@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, process};
+
+use crate::template::{
+    answers::{self, Verdict},
+    input,
+    registry::{self, Entry},
+    report::Status,
+    runner::RunOptions,
+    Puzzle, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Runs a day against every input in `dir` and prints a matrix of the results.
+///
+/// The expected answers of an input are read from a file next to it with the same name and a
+/// `.json` extension, in the format of `data/answers/`, e.g. `alice.txt` and `alice.json`.
+/// Exits with a non-zero status if any answer differs.
+///
+/// `timeout` limits the first run of each stage, see [`RunOptions::timeout`].
+pub fn handle(puzzle: Puzzle, dir: &Path, timeout: Option<Duration>, solutions: &[Entry]) {
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        eprintln!("Day {} has no solution, scaffold it first.", puzzle.day);
+        process::exit(1);
+    };
+
+    let inputs = match list_inputs(dir) {
+        Ok(inputs) if inputs.is_empty() => {
+            eprintln!("No inputs found in {}.", dir.display());
+            process::exit(1);
+        }
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("Failed to list inputs in {}: {e}", dir.display());
+            process::exit(1);
+        }
+    };
+
+    let options = RunOptions {
+        timeout,
+        ..RunOptions::default()
+    };
+    let mut rows: Vec<(String, Vec<String>)> = vec![];
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+
+    for path in &inputs {
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |x| x.to_string_lossy().into(),
+        );
+
+        let input = match input::read_path(path.clone()) {
+            Ok(input) => input,
+            Err(e) => {
+                failed += 1;
+                rows.push((name, vec![format!("{ANSI_BOLD}ERROR{ANSI_RESET} ({e})")]));
+                continue;
+            }
+        };
+
+        let expected = match answers::load_path(&path.with_extension("json")) {
+            Ok(expected) => expected,
+            Err(e) => {
+                eprintln!("{name}: {e}");
+                process::exit(1);
+            }
+        };
+
+        let mut reports = vec![];
+        solution.run_isolated(input, &options, &mut |report| reports.push(report.clone()));
+
+        // a failed parse stage takes the place of the parts, which did not run.
+        let parse_error = reports
+            .iter()
+            .find(|x| x.part.is_none() && x.status == Status::Failed);
+
+        let cells = (0..)
+            .zip(solution.parts())
+            .map(|(column, &part)| {
+                let Some(report) = reports.iter().find(|x| x.part == Some(part)) else {
+                    return match (column, parse_error) {
+                        (0, Some(report)) => {
+                            failed += 1;
+                            let error = report.error.as_deref().unwrap_or_default();
+                            format!("{ANSI_BOLD}ERROR{ANSI_RESET} (parse {error})")
+                        }
+                        _ => String::new(),
+                    };
+                };
+
+                if report.status == Status::Failed {
+                    failed += 1;
+                    let error = report.error.as_deref().unwrap_or_default();
+                    return format!("{ANSI_BOLD}ERROR{ANSI_RESET} ({error})");
+                }
+
+                match answers::verify(expected.get(part), report.answer.as_deref()) {
+                    Verdict::Pass => {
+                        passed += 1;
+                        "pass".to_string()
+                    }
+                    Verdict::Unknown => {
+                        unknown += 1;
+                        let actual = report.answer.as_deref().unwrap_or("✖");
+                        format!("unknown ({actual})")
+                    }
+                    Verdict::Fail { expected, actual } => {
+                        failed += 1;
+                        let actual = actual.as_deref().unwrap_or("✖");
+                        format!("{ANSI_BOLD}FAIL{ANSI_RESET} (expected {expected}, got {actual})")
+                    }
+                }
+            })
+            .collect();
+
+        rows.push((name, cells));
+    }
+
+    print_matrix(solution.parts(), &rows);
+
+    println!();
+    println!("{ANSI_BOLD}{passed} passed, {failed} failed, {unknown} unknown.{ANSI_RESET}");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Lists the inputs in `dir`, i.e. every file that does not hold expected answers.
+fn list_inputs(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut inputs: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_none_or(|x| x != "json"))
+        .collect();
+
+    inputs.sort_unstable();
+    Ok(inputs)
+}
+
+/// Prints a row per input with a column per part, labelled with the number of the part.
+fn print_matrix(parts: &[u8], rows: &[(String, Vec<String>)]) {
+    let header: Vec<String> = ["Input".to_string()]
+        .into_iter()
+        .chain(parts.iter().map(|part| format!("Part {part}")))
+        .collect();
+
+    let lines: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(name, cells)| [name.clone()].into_iter().chain(cells.clone()).collect())
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            [&header]
+                .into_iter()
+                .chain(&lines)
+                .filter_map(|line| line.get(i))
+                .map(|x| visible_len(x))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_line = |line: &[String]| {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - visible_len(cell))))
+            .collect();
+        cells.join("  ").trim_end().to_string()
+    };
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_line(&header));
+
+    for line in &lines {
+        println!("{}", format_line(line));
+    }
+}
+
+/// The number of characters of a string, without ANSI escape codes.
+fn visible_len(s: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+
+    for c in s.chars() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (true, 'm') => in_escape = false,
+            (false, _) => len += 1,
+            (true, _) => {}
+        }
+    }
+
+    len
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::visible_len;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn ignores_escape_codes_in_width() {
+        assert_eq!(visible_len("pass"), 4);
+        assert_eq!(visible_len(&format!("{ANSI_BOLD}FAIL{ANSI_RESET} (1)")), 8);
+    }
+}
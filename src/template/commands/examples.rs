@@ -0,0 +1,80 @@
+use std::{fs, process};
+
+use crate::template::{
+    examples::{self, Outcome},
+    input::download_command,
+    Puzzle,
+};
+
+/// Extracts the examples of a downloaded puzzle description into `data/examples/`.
+/// Existing example files are kept, but their missing answers are recorded. An empty example file
+/// created by `scaffold` receives the first example.
+pub fn handle(puzzle: Puzzle) {
+    let puzzle_path = puzzle.data_path("puzzles", ".md");
+
+    let Ok(html) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "The description of {puzzle} is missing, run `{}` first.",
+            download_command(puzzle)
+        );
+        process::exit(1);
+    };
+
+    let extracted = examples::extract(&html);
+
+    if extracted.is_empty() {
+        println!("No examples found in \"{puzzle_path}\".");
+        return;
+    }
+
+    for (example, contents) in (1..).zip(&extracted) {
+        let path = examples::get_path(puzzle, example, ".txt");
+
+        match examples::write(puzzle, example, contents) {
+            Ok(Outcome::Created) => println!(
+                "🎄 Extracted example to \"{path}\" ({}).",
+                describe_answers(contents)
+            ),
+            Ok(Outcome::Updated) => println!(
+                "🎄 Kept existing example file \"{path}\", recorded missing answers ({}).",
+                describe_answers(contents)
+            ),
+            Ok(Outcome::Unchanged) => println!("Kept existing example file \"{path}\"."),
+            Err(e) => {
+                eprintln!("Failed to write example: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    fill_scaffolded_example(puzzle, &extracted[0]);
+}
+
+fn describe_answers(example: &examples::Example) -> String {
+    let answers: Vec<String> = (1..=2)
+        .filter_map(|part| {
+            let answer = example.answers.get(part)?;
+            Some(format!("part {part}: {answer}"))
+        })
+        .collect();
+
+    if answers.is_empty() {
+        "no answers found".into()
+    } else {
+        answers.join(", ")
+    }
+}
+
+/// Writes the first example to `NN.txt`, which the solution template reads, if it is empty.
+fn fill_scaffolded_example(puzzle: Puzzle, example: &examples::Example) {
+    let path = puzzle.data_path("examples", ".txt");
+
+    if !fs::read_to_string(&path).is_ok_and(|x| x.trim().is_empty()) {
+        return;
+    }
+
+    match fs::write(&path, &example.input) {
+        Ok(()) => println!("🎄 Copied the first example to \"{path}\"."),
+        Err(e) => eprintln!("Failed to write example file: {e}"),
+    }
+}
@@ -0,0 +1,37 @@
+use std::process;
+
+use crate::template::dates::format_timestamp;
+use crate::template::submissions::{self, Attempt};
+use crate::template::Puzzle;
+
+pub fn handle(puzzle: Puzzle) {
+    let history = match submissions::load(puzzle) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("failed to load submission history: {e}");
+            process::exit(1);
+        }
+    };
+
+    if history.attempts.is_empty() {
+        println!("No submissions for day {} yet.", puzzle.day);
+        return;
+    }
+
+    for part in [1, 2] {
+        let attempts: Vec<&Attempt> = history.for_part(part).collect();
+        if attempts.is_empty() {
+            continue;
+        }
+
+        println!("Day {} part {part}:", puzzle.day);
+        for attempt in attempts {
+            println!(
+                "  {} {:>16}  {}",
+                format_timestamp(attempt.submitted_at),
+                attempt.answer,
+                attempt.verdict
+            );
+        }
+    }
+}
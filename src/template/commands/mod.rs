@@ -1,5 +1,11 @@
 pub mod all;
+pub mod bench_diff;
+pub mod cross_validate;
 pub mod download;
+pub mod examples;
+pub mod history;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod verify;
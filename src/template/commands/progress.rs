@@ -0,0 +1,47 @@
+use std::{fs, process};
+
+use crate::template::{config, progress, readme_benchmarks, Year};
+
+/// Prints the stars of a year and, for the configured year, renders them into the readme.
+pub fn handle(year: Year) {
+    let progress = progress::load(year);
+
+    for stars in progress.days.iter().filter(|x| x.count() > 0) {
+        println!("Day {}: {}", stars.day, "⭐️".repeat(stars.count().into()));
+    }
+    println!("{} of 50 stars in {year}.", progress.total());
+
+    if year != Year::configured() {
+        eprintln!(
+            "Skipped updating the readme with progress, since it tracks the configured year."
+        );
+        return;
+    }
+
+    let config = config::get();
+    let path = config.resolve(&config.readme.path);
+
+    let result = fs::read_to_string(&path)
+        .map_err(readme_benchmarks::Error::from)
+        .and_then(|mut readme| {
+            let calendar = progress::construct_calendar("##", &progress);
+            readme_benchmarks::replace_block(
+                &mut readme,
+                &config.readme.progress_marker,
+                &calendar,
+            )?;
+            fs::write(&path, readme)?;
+            Ok(())
+        });
+
+    match result {
+        Ok(()) => println!("Successfully updated README with progress."),
+        Err(e) => {
+            eprintln!(
+                "Failed to update readme with progress: {e}. Add two `{}` lines to it first.",
+                config.readme.progress_marker
+            );
+            process::exit(1);
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{config, Puzzle};
 
+/// The template that is used if the configured template file does not exist.
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
@@ -14,13 +16,37 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle) {
+    let input_path = puzzle.data_path("inputs", ".txt");
+    let example_path = puzzle.data_path("examples", ".txt");
+    let module_path = puzzle.bin_path();
+
+    for path in [&input_path, &example_path] {
+        if let Some(parent) = Path::new(path).parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                eprintln!("Failed to create data folder: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    // solutions of other years need to read their examples from the year-scoped folder.
+    let puzzle_expr = if puzzle.is_scoped() {
+        "SOLUTION.puzzle()"
+    } else {
+        "DAY"
+    };
+
+    let config = config::get();
+    let template = fs::read_to_string(config.resolve(&config.paths.template))
+        .unwrap_or_else(|_| MODULE_TEMPLATE.into());
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -31,8 +57,9 @@ pub fn handle(day: Day) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        template
+            .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+            .replace("%PUZZLE%", puzzle_expr)
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -65,5 +92,12 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    if puzzle.is_scoped() {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    } else {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    }
 }
@@ -1,30 +1,83 @@
-use std::process::{Command, Stdio};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
-use crate::template::Day;
+use crate::template::{
+    input::Source, params::Param, report::Format, runner::BenchConfig, stats::Statistic, Puzzle,
+};
 
-pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Flags of the `solve` command. Most of them are forwarded to the solution binary.
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub dhat: bool,
+    /// Count the allocations of every part, see [`alloc`](crate::template::alloc).
+    pub allocs: bool,
+    pub submit: Option<u8>,
+    pub record: bool,
+    pub stat: Statistic,
+    pub bench: BenchConfig,
+    pub format: Format,
+    /// Overrides of the solution's parameters.
+    pub params: Vec<Param>,
+    pub input: Source,
+    /// The time the first run of a stage may take, `None` if it is not limited.
+    pub timeout: Option<Duration>,
+    /// Run against every input in this directory instead, see [`cross_validate`](super::cross_validate).
+    pub inputs_dir: Option<PathBuf>,
+}
+
+pub fn handle(puzzle: Puzzle, options: &Options) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
-    if dhat {
+    if options.dhat {
         cmd_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if options.release {
+            cmd_args.push("--release".to_string());
+        }
+        if options.allocs {
+            cmd_args.push("--features".to_string());
+            cmd_args.push("count-allocations".to_string());
+        }
     }
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    cmd_args.push("--format".to_string());
+    cmd_args.push(options.format.to_string());
+
+    if let Some(submit_part) = options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    if options.record {
+        cmd_args.push("--record".to_string());
+    }
+
+    cmd_args.extend(options.input.to_args());
+
+    // `0` disables the timeout in the solution binary.
+    let timeout = options.timeout.map_or(0, |x| x.as_secs());
+    cmd_args.push("--timeout".to_string());
+    cmd_args.push(timeout.to_string());
+
+    for param in &options.params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.to_string());
+    }
+
+    if options.time {
         cmd_args.push("--time".to_string());
+        cmd_args.push("--stat".to_string());
+        cmd_args.push(options.stat.to_string());
+        cmd_args.extend(options.bench.to_args());
     }
 
     let mut cmd = Command::new("cargo")
@@ -34,5 +87,9 @@ pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Opti
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -0,0 +1,93 @@
+use std::process;
+
+use crate::template::{
+    all_days,
+    answers::{self, Answers, Verdict},
+    commands::all::run_day,
+    config,
+    registry::Entry,
+    report::Status,
+    runner::RunOptions,
+    Puzzle, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Runs every scaffolded day and compares its answers with the ones stored in `data/answers/`.
+/// Exits with a non-zero status if any answer differs, any stage failed, or a day with stored
+/// answers could not be run, e.g. because its input is missing.
+pub fn handle(year: Year, solutions: &[Entry]) {
+    let (mut passed, mut failed, mut unknown, mut unverified) = (0, 0, 0, 0);
+
+    let options = RunOptions {
+        timeout: config::get().timeout,
+        ..RunOptions::default()
+    };
+
+    for day in all_days() {
+        let puzzle = Puzzle::new(year, day);
+        let reports = run_day(solutions, puzzle, &options, &mut |_| {});
+
+        let expected = match answers::load(puzzle) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                process::exit(1);
+            }
+        };
+
+        // `run_day` has printed why the day did not run.
+        if reports.is_empty() {
+            if expected != Answers::default() {
+                unverified += 1;
+                println!("Day {day}: {ANSI_BOLD}UNVERIFIED{ANSI_RESET} (answers are stored, but the day could not run)");
+            }
+            continue;
+        }
+
+        for report in reports {
+            let stage = match report.part {
+                Some(part) => format!("part {part}"),
+                None => "parse".into(),
+            };
+
+            // a failed parse stage has no answer, but its parts did not run either.
+            if report.status == Status::Failed {
+                failed += 1;
+                let error = report.error.as_deref().unwrap_or_default();
+                println!("Day {day} {stage}: {ANSI_BOLD}FAIL{ANSI_RESET} ({error})");
+                continue;
+            }
+
+            let Some(part) = report.part else {
+                continue;
+            };
+
+            let verdict = answers::verify(expected.get(part), report.answer.as_deref());
+            let line = match &verdict {
+                Verdict::Pass => {
+                    passed += 1;
+                    "pass".to_string()
+                }
+                Verdict::Unknown => {
+                    unknown += 1;
+                    "unknown".to_string()
+                }
+                Verdict::Fail { expected, actual } => {
+                    failed += 1;
+                    let actual = actual.as_deref().unwrap_or("✖");
+                    format!("{ANSI_BOLD}FAIL{ANSI_RESET} (expected {expected}, got {actual})")
+                }
+            };
+
+            println!("Day {day} {stage}: {line}");
+        }
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{passed} passed, {failed} failed, {unknown} unknown, {unverified} unverified.{ANSI_RESET}"
+    );
+
+    if failed > 0 || unverified > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,393 @@
+/// Project configuration, read from an `aoc.toml` file in the project root.
+///
+/// The file is discovered by walking up from the working directory and loaded once per process.
+/// Every setting is optional. Environment variables take precedence over the file:
+///  - `AOC_YEAR` overrides `year`.
+///  - `AOC_SESSION` and `AOC_SESSION_FILE` override `session_file`.
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::template::readme_benchmarks::{Column, Sort};
+use crate::template::runner::BenchConfig;
+use crate::template::Year;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+const DEFAULT_REGRESSION_THRESHOLD: u32 = 10;
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    Parser(toml::de::Error),
+    InvalidYear(u16),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Error::Parser(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "could not read {CONFIG_FILE}: {e}"),
+            Error::Parser(e) => write!(f, "could not parse {CONFIG_FILE}: {e}"),
+            Error::InvalidYear(year) => {
+                write!(
+                    f,
+                    "invalid year {year} in {CONFIG_FILE}, expecting 2015 or later"
+                )
+            }
+        }
+    }
+}
+
+/// The resolved configuration of the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The directory that contains `aoc.toml`, relative to the working directory if it is inside.
+    /// Empty if the file is in the working directory.
+    pub root: PathBuf,
+    pub year: Option<Year>,
+    pub paths: Paths,
+    pub bench: BenchConfig,
+    pub session_file: Option<PathBuf>,
+    pub readme: Readme,
+    /// How long a stage of a solution may run before it is reported as failed.
+    pub timeout: Option<Duration>,
+    /// The slowdown in percent above which `bench-diff` reports a stage as a regression.
+    pub regression_threshold: u32,
+}
+
+/// The folders that contain the data files of the configured year.
+/// Other years use a folder named after the year next to each of them, e.g. `data/2022/inputs`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    pub answers: PathBuf,
+    pub submissions: PathBuf,
+    /// The template that `scaffold` creates solutions from.
+    pub template: PathBuf,
+    /// The benchmark history of `cargo time`, see [`bench_history`](super::bench_history).
+    pub benchmarks: PathBuf,
+    /// The sources of the last runs for `cargo all --changed`, see [`fingerprints`](super::fingerprints).
+    pub fingerprints: PathBuf,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            inputs: "data/inputs".into(),
+            examples: "data/examples".into(),
+            puzzles: "data/puzzles".into(),
+            answers: "data/answers".into(),
+            submissions: "data/submissions".into(),
+            template: "src/template.txt".into(),
+            benchmarks: "data/benchmarks.json".into(),
+            fingerprints: "data/fingerprints.json".into(),
+        }
+    }
+}
+
+impl Paths {
+    /// The configured folder for a kind of data file, or `data/<folder>` for unknown kinds.
+    pub fn folder(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            "answers" => self.answers.clone(),
+            "submissions" => self.submissions.clone(),
+            _ => Path::new("data").join(folder),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Readme {
+    pub path: PathBuf,
+    /// The comment that encloses the benchmark table.
+    pub marker: String,
+    /// The comment that encloses the ⭐️ progress calendar of `cargo progress`.
+    pub progress_marker: String,
+    /// The columns of the benchmark table after the day, see [`Column`].
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+}
+
+impl Default for Readme {
+    fn default() -> Self {
+        Self {
+            path: "README.md".into(),
+            marker: "<!--- benchmarking table --->".into(),
+            progress_marker: "<!--- progress table --->".into(),
+            columns: Column::defaults(),
+            sort: Sort::default(),
+        }
+    }
+}
+
+/// The contents of `aoc.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<u16>,
+    paths: Paths,
+    bench: BenchFile,
+    session_file: Option<PathBuf>,
+    readme: Readme,
+    /// In seconds, `0` disables the timeout.
+    timeout: Option<u64>,
+}
+
+/// The `[bench]` table, durations are given in milliseconds.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct BenchFile {
+    warmup: Option<u64>,
+    time: Option<u64>,
+    min_samples: Option<u128>,
+    max_samples: Option<u128>,
+    /// In percent.
+    regression_threshold: Option<u32>,
+}
+
+impl Config {
+    /// Parses the contents of a config file located in `root`.
+    pub fn parse(root: &Path, contents: &str) -> Result<Self, Error> {
+        let file: ConfigFile = toml::from_str(contents)?;
+
+        let year = file
+            .year
+            .map(|x| Year::new(x).ok_or(Error::InvalidYear(x)))
+            .transpose()?;
+
+        let default_bench = BenchConfig::default();
+
+        let bench = BenchConfig {
+            warmup: file
+                .bench
+                .warmup
+                .map_or(default_bench.warmup, Duration::from_millis),
+            target: file
+                .bench
+                .time
+                .map_or(default_bench.target, Duration::from_millis),
+            min_samples: file.bench.min_samples.unwrap_or(default_bench.min_samples),
+            max_samples: file.bench.max_samples.unwrap_or(default_bench.max_samples),
+        };
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            year,
+            paths: file.paths,
+            bench,
+            session_file: file.session_file.map(|x| expand_home(&x)),
+            readme: file.readme,
+            timeout: match file.timeout {
+                Some(0) => None,
+                Some(secs) => Some(Duration::from_secs(secs)),
+                None => Some(DEFAULT_TIMEOUT),
+            },
+            regression_threshold: file
+                .bench
+                .regression_threshold
+                .unwrap_or(DEFAULT_REGRESSION_THRESHOLD),
+        })
+    }
+
+    /// Resolves a path from the config file, which is relative to the project root.
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.root.join(path)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            year: None,
+            paths: Paths::default(),
+            bench: BenchConfig::default(),
+            session_file: None,
+            readme: Readme::default(),
+            timeout: Some(DEFAULT_TIMEOUT),
+            regression_threshold: DEFAULT_REGRESSION_THRESHOLD,
+        }
+    }
+}
+
+/// Returns the configuration of the project. Exits the process if `aoc.toml` is invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| match load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load configuration: {e}");
+            process::exit(1);
+        }
+    })
+}
+
+/// Looks for `aoc.toml` in the working directory and its ancestors, and then in the package
+/// directory. Returns the default configuration, rooted in the package directory, if there is none.
+pub fn load() -> Result<Config, Error> {
+    let cwd = env::current_dir()?;
+    let manifest_dir = manifest_dir();
+
+    let Some(path) = cwd
+        .ancestors()
+        .chain([manifest_dir.as_path()])
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+    else {
+        return Ok(Config {
+            root: relative_to(&manifest_dir, &cwd),
+            ..Config::default()
+        });
+    };
+
+    let dir = path.parent().unwrap_or(&cwd);
+    Config::parse(&relative_to(dir, &cwd), &fs::read_to_string(&path)?)
+}
+
+/// The directory of the package. Cargo passes it to the processes it runs, e.g. a test binary that
+/// is launched from another directory. Falls back to the directory the template was built in.
+fn manifest_dir() -> PathBuf {
+    env::var_os("CARGO_MANIFEST_DIR")
+        .map_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")), PathBuf::from)
+}
+
+/// Strips the working directory from a path, so paths stay short in messages.
+fn relative_to(path: &Path, cwd: &Path) -> PathBuf {
+    path.strip_prefix(cwd).unwrap_or(path).to_path_buf()
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var("HOME")) {
+        (Ok(rest), Ok(home)) => Path::new(&home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Config, Error};
+    use crate::template::readme_benchmarks::Sort;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_empty_config() {
+        let config = Config::parse(Path::new(""), "").unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn parses_full_config() {
+        let config = Config::parse(
+            Path::new(".."),
+            r#"
+year = 2022
+session_file = "/secrets/aoc"
+timeout = 0
+
+[paths]
+inputs = "private/inputs"
+template = "template.rs"
+benchmarks = "private/benchmarks.json"
+
+[bench]
+warmup = 50
+time = 2000
+regression_threshold = 25
+
+[readme]
+marker = "<!-- bench -->"
+columns = ["part_1", "part_1_stddev", "part_2_samples", "total"]
+sort = "slowest"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year.unwrap().into_inner(), 2022);
+        assert_eq!(config.session_file, Some(PathBuf::from("/secrets/aoc")));
+        assert_eq!(config.timeout, None);
+        assert_eq!(
+            config.paths.folder("inputs"),
+            PathBuf::from("private/inputs")
+        );
+        assert_eq!(
+            config.paths.folder("examples"),
+            PathBuf::from("data/examples")
+        );
+        assert_eq!(config.bench.warmup, Duration::from_millis(50));
+        assert_eq!(config.bench.target, Duration::from_secs(2));
+        assert_eq!(config.bench.min_samples, 10);
+        assert_eq!(config.regression_threshold, 25);
+        assert_eq!(config.readme.marker, "<!-- bench -->");
+        assert_eq!(config.readme.path, PathBuf::from("README.md"));
+        assert_eq!(
+            config
+                .readme
+                .columns
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["part_1", "part_1_stddev", "part_2_samples", "total"]
+        );
+        assert_eq!(config.readme.sort, Sort::Slowest);
+        assert_eq!(
+            config.resolve(&config.paths.template),
+            PathBuf::from("../template.rs")
+        );
+        assert_eq!(
+            config.resolve(&config.paths.benchmarks),
+            PathBuf::from("../private/benchmarks.json")
+        );
+        assert_eq!(
+            config.resolve(&config.paths.fingerprints),
+            PathBuf::from("../data/fingerprints.json")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let root = Path::new("");
+        assert!(matches!(
+            Config::parse(root, "year = 1999"),
+            Err(Error::InvalidYear(1999))
+        ));
+        assert!(matches!(
+            Config::parse(root, "yaer = 2022"),
+            Err(Error::Parser(_))
+        ));
+        assert!(matches!(
+            Config::parse(root, "[readme]\ncolumns = [\"part_3\"]"),
+            Err(Error::Parser(_))
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+/// Dates of the proleptic Gregorian calendar in UTC, for the timestamps that the template stores
+/// and for finding the weekdays and years of the events.
+/// The conversions follow http://howardhinnant.github.io/date_algorithms.html.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i64,
+    /// `1` for January.
+    pub month: i64,
+    pub day: i64,
+}
+
+const SECS_PER_DAY: u64 = 86400;
+
+impl Date {
+    /// The date of a time given in seconds since the unix epoch.
+    pub fn from_timestamp(secs: u64) -> Self {
+        Self::from_days(i64::try_from(secs / SECS_PER_DAY).unwrap_or(0))
+    }
+
+    /// The date that is `days` days after the unix epoch.
+    pub fn from_days(days: i64) -> Self {
+        // eras of 400 years start on the 1st of March, so leap days are at the end of a year.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self { year, month, day }
+    }
+
+    /// The number of days since the unix epoch.
+    pub fn days(self) -> i64 {
+        let year = self.year - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = (self.month + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// The day of the week, `0` being Monday.
+    pub fn weekday(self) -> usize {
+        // the epoch was a Thursday.
+        usize::try_from((self.days() + 3).rem_euclid(7)).unwrap_or(0)
+    }
+}
+
+/// Formats seconds since the unix epoch as a UTC date and time, e.g. `2023-12-01 05:00:12`.
+pub fn format_timestamp(secs: u64) -> String {
+    let Date { year, month, day } = Date::from_timestamp(secs);
+    let time = secs % SECS_PER_DAY;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, Date};
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1_701_406_812), "2023-12-01 05:00:12");
+        assert_eq!(format_timestamp(1_709_251_199), "2024-02-29 23:59:59");
+    }
+
+    #[test]
+    fn converts_days() {
+        for days in [-719_468, -1, 0, 59, 19_692, 20_058, 2_932_896] {
+            assert_eq!(Date::from_days(days).days(), days);
+        }
+
+        let date = Date {
+            year: 2024,
+            month: 2,
+            day: 29,
+        };
+        assert_eq!(Date::from_days(date.days()), date);
+    }
+
+    #[test]
+    fn finds_weekdays() {
+        // 2015-12-01 was a Tuesday, 2023-12-01 a Friday and 2024-12-01 a Sunday.
+        let december_1st = |year| Date {
+            year,
+            month: 12,
+            day: 1,
+        };
+        assert_eq!(december_1st(2015).weekday(), 1);
+        assert_eq!(december_1st(2023).weekday(), 4);
+        assert_eq!(december_1st(2024).weekday(), 6);
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -57,6 +58,21 @@ impl PartialOrd<u8> for Day {
 
 /* -------------------------------------------------------------------------- */
 
+impl Serialize for Day {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Day {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let day = u8::deserialize(deserializer)?;
+        Self::new(day).ok_or_else(|| de::Error::custom(DayFromStrError))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 impl FromStr for Day {
     type Err = DayFromStrError;
 
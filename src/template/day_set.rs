@@ -0,0 +1,140 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{all_days, Day, DayFromStrError};
+
+/// A selection of days, e.g. `3-7` or `1,5,12`.
+///
+/// Parses a comma-separated list of days and inclusive ranges of days.
+///
+/// ```
+/// # use advent_of_code::template::{Day, DaySet};
+/// let days: DaySet = "1,3-5".parse().unwrap();
+/// assert_eq!(days.iter().map(Day::into_inner).collect::<Vec<_>>(), [1, 3, 4, 5]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(BTreeSet<Day>);
+
+impl DaySet {
+    /// A set that contains every day of advent.
+    pub fn all() -> Self {
+        Self(all_days().collect())
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+
+    pub fn is_all(&self) -> bool {
+        self.0.len() == 25
+    }
+
+    /// Iterates the days of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Keeps only the days for which `f` returns `true`.
+    pub fn retain(&mut self, f: impl FnMut(&Day) -> bool) {
+        self.0.retain(f);
+    }
+}
+
+impl Default for DaySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',').map(str::trim) {
+            let parse_day = |x: &str| {
+                x.trim()
+                    .parse::<Day>()
+                    .map_err(|e| DaySetFromStrError::Day(x.trim().into(), e))
+            };
+
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_day(start)?, parse_day(end)?);
+                    if start > end {
+                        return Err(DaySetFromStrError::EmptyRange(item.into()));
+                    }
+                    days.extend(all_days().filter(|day| (start..=end).contains(day)));
+                }
+                None => {
+                    days.insert(parse_day(item)?);
+                }
+            }
+        }
+
+        Ok(Self(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub enum DaySetFromStrError {
+    Day(String, DayFromStrError),
+    EmptyRange(String),
+}
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySetFromStrError::Day(item, e) => write!(f, "invalid day `{item}`: {e}"),
+            DaySetFromStrError::EmptyRange(item) => {
+                write!(f, "invalid range `{item}`: the start is after the end")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+
+    fn to_vec(days: &DaySet) -> Vec<u8> {
+        days.iter().map(|x| x.into_inner()).collect()
+    }
+
+    #[test]
+    fn parses_lists_and_ranges() {
+        assert_eq!(to_vec(&"3-7".parse().unwrap()), [3, 4, 5, 6, 7]);
+        assert_eq!(to_vec(&"12,1,5".parse().unwrap()), [1, 5, 12]);
+        assert_eq!(to_vec(&"1, 3-4,4".parse().unwrap()), [1, 3, 4]);
+        assert_eq!(to_vec(&"25-25".parse().unwrap()), [25]);
+    }
+
+    #[test]
+    fn rejects_invalid_sets() {
+        assert!("0-3".parse::<DaySet>().is_err());
+        assert!("7-3".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+        assert!("26".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn defaults_to_all_days() {
+        let days = DaySet::default();
+        assert!(days.is_all());
+        assert_eq!(days.iter().count(), 25);
+    }
+}
@@ -0,0 +1,231 @@
+/// Extracts the examples of a puzzle and their expected answers from the description that
+/// `cargo download` stores in `data/puzzles/`.
+///
+/// Each example is written to `data/examples/NN-k.txt` and its answers to `data/examples/NN-k.json`,
+/// which tests can read with [`crate::template::read_example_answer`].
+use std::sync::OnceLock;
+use std::{fs, path::Path};
+
+use regex::Regex;
+
+use crate::template::answers::{self, Answers};
+use crate::template::aoc_client::decode_entities;
+use crate::template::json_store::{self, Error};
+use crate::template::Puzzle;
+
+/// An example input of a puzzle and the answers the description gives for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+/// The path of the numbered example `NN-k` with the given suffix, e.g. `.txt` or `.json`.
+#[must_use]
+pub fn get_path(puzzle: Puzzle, example: u8, suffix: &str) -> String {
+    puzzle.data_path("examples", &format!("-{example}{suffix}"))
+}
+
+/// Loads the answers recorded for a numbered example. Returns empty [`Answers`] if there are none.
+pub fn load_answers(puzzle: Puzzle, example: u8) -> Result<Answers, Error> {
+    answers::load_path(Path::new(&get_path(puzzle, example, ".json")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds the examples in the HTML of a puzzle description, in order of appearance.
+///
+/// Examples are the `<pre><code>` blocks of the description. The answer of a part is the last
+/// emphasised `<code><em>` after an example and before the next one, which is how the puzzles
+/// state their results. The second part usually refers back to an earlier example, so answers
+/// that precede its first block are attributed to the last example of the first part.
+/// Blocks that repeat an earlier example are merged into it.
+pub fn extract(html: &str) -> Vec<Example> {
+    static ARTICLES: OnceLock<Regex> = OnceLock::new();
+    static TOKENS: OnceLock<Regex> = OnceLock::new();
+
+    let articles =
+        ARTICLES.get_or_init(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+    let tokens = TOKENS.get_or_init(|| {
+        Regex::new(
+            r"(?s)<pre><code>(?P<block>.*?)</code></pre>|<code><em>(?P<answer>.*?)</em></code>|<em><code>(?P<alt>.*?)</code></em>",
+        )
+        .unwrap()
+    });
+
+    let mut articles: Vec<&str> = articles
+        .captures_iter(html)
+        .filter_map(|x| x.get(1).map(|x| x.as_str()))
+        .collect();
+
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    let mut examples: Vec<Example> = vec![];
+
+    for (part, article) in (1..=2).zip(articles) {
+        let mut current = examples.len().checked_sub(1);
+
+        for token in tokens.captures_iter(article) {
+            if let Some(block) = token.name("block") {
+                let input = strip_tags(block.as_str());
+
+                current = match examples.iter().position(|x| x.input == input) {
+                    Some(index) => Some(index),
+                    None => {
+                        examples.push(Example {
+                            input,
+                            answers: Answers::default(),
+                        });
+                        Some(examples.len() - 1)
+                    }
+                };
+            } else if let Some(answer) = token.name("answer").or_else(|| token.name("alt")) {
+                let answer = strip_tags(answer.as_str());
+                let answer = answer.trim();
+
+                if let (Some(index), false) = (current, answer.is_empty()) {
+                    examples[index].answers.set(part, answer);
+                }
+            }
+        }
+    }
+
+    examples
+}
+
+/// Removes the emphasis that some examples use to highlight parts of the input.
+fn strip_tags(html: &str) -> String {
+    static TAGS: OnceLock<Regex> = OnceLock::new();
+
+    let tags = TAGS.get_or_init(|| Regex::new(r"<[^>]*>").unwrap());
+    decode_entities(&tags.replace_all(html, ""))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What [`write`] did with an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The example file did not exist or was empty, and was written with its answers.
+    Created,
+    /// The example file was kept, answers that were missing from it were recorded.
+    Updated,
+    /// The example file was kept and all of its answers were known already.
+    Unchanged,
+}
+
+/// Writes the `k`-th example (starting at `1`) and its answers. A non-empty example file is kept,
+/// but answers that are missing from its answers file are filled in, e.g. those of part two
+/// once it was unlocked.
+pub fn write(puzzle: Puzzle, example: u8, contents: &Example) -> Result<Outcome, Error> {
+    let input_path = get_path(puzzle, example, ".txt");
+    let exists = fs::read_to_string(&input_path).is_ok_and(|x| !x.trim().is_empty());
+
+    if !exists {
+        json_store::write(Path::new(&input_path), &contents.input)?;
+    }
+
+    let mut answers = load_answers(puzzle, example)?;
+    let is_updated = merge_answers(&mut answers, &contents.answers);
+
+    if is_updated {
+        json_store::save(Path::new(&get_path(puzzle, example, ".json")), &answers)?;
+    }
+
+    Ok(match (exists, is_updated) {
+        (false, _) => Outcome::Created,
+        (true, true) => Outcome::Updated,
+        (true, false) => Outcome::Unchanged,
+    })
+}
+
+/// Fills in the parts that are missing from `answers` with those of `extracted`, known answers
+/// are kept.
+/// Returns whether any answer was added.
+fn merge_answers(answers: &mut Answers, extracted: &Answers) -> bool {
+    let mut is_updated = false;
+
+    for part in 1..=2 {
+        if let (None, Some(answer)) = (answers.get(part), extracted.get(part)) {
+            answers.set(part, answer);
+            is_updated = true;
+        }
+    }
+
+    is_updated
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, merge_answers, Example};
+    use crate::template::answers::Answers;
+
+    const MOCK_PUZZLE: &str = r"<article class='day-desc'><h2>--- Day 1: Test ---</h2>
+<p>For example, <code><em>ignored</em></code> before any example:</p>
+<pre><code>1 &lt; 2
+<em>3</em> 4
+</code></pre>
+<p>Here, the sum is <code>10</code> and the result is <code><em>142</em></code>.</p>
+</article>
+
+<article class='day-desc'><h2 id='part2'>--- Part Two ---</h2>
+<p>In the above example, the result is now <code><em>281</em></code>.</p>
+<pre><code>a
+b
+</code></pre>
+<p>In this example, the result is <em><code>7</code></em>.</p>
+<pre><code>1 &lt; 2
+<em>3</em> 4
+</code></pre>
+</article>
+";
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        assert_eq!(
+            extract(MOCK_PUZZLE),
+            vec![
+                Example {
+                    input: "1 < 2\n3 4\n".into(),
+                    answers: Answers {
+                        part_1: Some("142".into()),
+                        part_2: Some("281".into()),
+                    },
+                },
+                Example {
+                    input: "a\nb\n".into(),
+                    answers: Answers {
+                        part_1: None,
+                        part_2: Some("7".into()),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_nothing_without_examples() {
+        assert!(extract("<article><p>No example.</p></article>").is_empty());
+    }
+
+    #[test]
+    fn merges_missing_answers() {
+        let mut answers = Answers {
+            part_1: Some("142".into()),
+            part_2: None,
+        };
+        let extracted = Answers {
+            part_1: Some("0".into()),
+            part_2: Some("281".into()),
+        };
+
+        assert!(merge_answers(&mut answers, &extracted));
+        assert_eq!(answers.part_1.as_deref(), Some("142"));
+        assert_eq!(answers.part_2.as_deref(), Some("281"));
+        assert!(!merge_answers(&mut answers, &extracted));
+    }
+}
@@ -0,0 +1,66 @@
+/// Remembers the source of every solution as of its last run in `data/fingerprints.json`,
+/// so `cargo all --changed` can skip the days that were not edited since. The file is configured
+/// with `paths.fingerprints`.
+use std::collections::BTreeMap;
+use std::{fs, path::PathBuf};
+
+use crate::template::json_store::{self, Error};
+use crate::template::{config, Puzzle};
+
+fn get_path() -> PathBuf {
+    let config = config::get();
+    config.resolve(&config.paths.fingerprints)
+}
+
+/// The fingerprint of each solution's source file, keyed by the name of its binary.
+pub type Fingerprints = BTreeMap<String, String>;
+
+/// Computes the fingerprint of a puzzle's current source file, if it exists.
+pub fn compute(puzzle: Puzzle) -> Option<String> {
+    fs::read(puzzle.bin_path()).ok().map(|x| hash(&x))
+}
+
+/// Whether the source of a puzzle differs from the one it was last run with.
+pub fn has_changed(fingerprints: &Fingerprints, puzzle: Puzzle) -> bool {
+    fingerprints.get(&puzzle.bin_name()) != compute(puzzle).as_ref()
+}
+
+/// Loads the stored fingerprints. Returns an empty map if no run was recorded yet.
+pub fn load() -> Result<Fingerprints, Error> {
+    json_store::load(&get_path())
+}
+
+/// Stores the current fingerprints of `puzzles`, keeping the ones of all other puzzles.
+pub fn record(puzzles: &[Puzzle]) -> Result<(), Error> {
+    let mut fingerprints = load()?;
+
+    for &puzzle in puzzles {
+        if let Some(fingerprint) = compute(puzzle) {
+            fingerprints.insert(puzzle.bin_name(), fingerprint);
+        }
+    }
+
+    json_store::save(&get_path(), &fingerprints)
+}
+
+/// 64-bit FNV-1a, which is stable across platforms and compiler versions unlike `DefaultHasher`.
+fn hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::hash;
+
+    #[test]
+    fn hashes_fnv1a() {
+        assert_eq!(hash(b""), "cbf29ce484222325");
+        assert_eq!(hash(b"a"), "af63dc4c8601ec8c");
+        assert_ne!(hash(b"part_one"), hash(b"part_two"));
+    }
+}
@@ -0,0 +1,229 @@
+/// Reads the data files of a puzzle, e.g. its input or examples.
+///
+/// Paths are resolved against the project root that contains `aoc.toml`, or else the package
+/// directory, so they work from any working directory, e.g. when an IDE launches a test binary.
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use crate::template::{aoc_client, runner::get_arg, Puzzle};
+
+#[derive(Debug)]
+pub enum Error {
+    Missing(PathBuf),
+    Empty(PathBuf),
+    IO(PathBuf, io::Error),
+}
+
+impl Error {
+    /// The path of the file that could not be read.
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            Error::Missing(path) | Error::Empty(path) | Error::IO(path, _) => path,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Missing(path) => write!(f, "{} does not exist", path.display()),
+            Error::Empty(path) => write!(f, "{} is empty", path.display()),
+            Error::IO(path, e) => write!(f, "could not read {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(feature = "embed-inputs")]
+include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+
+/// Reads a data file of a puzzle, e.g. `read("examples", puzzle, ".txt")`.
+/// Fails if the file does not exist, an empty file is read as is.
+///
+/// With the `embed-inputs` feature, files that existed at build time are read from the binary.
+pub fn read(folder: &str, puzzle: Puzzle, suffix: &str) -> Result<String, Error> {
+    #[cfg(feature = "embed-inputs")]
+    if let Some(contents) = embedded(&puzzle.data_file(folder, suffix)) {
+        return Ok(contents.to_string());
+    }
+
+    read_path(PathBuf::from(puzzle.data_path(folder, suffix)))
+}
+
+/// Reads a file at any path. Fails if the file does not exist.
+pub fn read_path(path: PathBuf) -> Result<String, Error> {
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::Missing(path)),
+        Err(e) => Err(Error::IO(path, e)),
+    }
+}
+
+#[cfg(feature = "embed-inputs")]
+fn embedded(file: &std::path::Path) -> Option<&'static str> {
+    let key = file
+        .components()
+        .map(|x| x.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    EMBEDDED
+        .iter()
+        .find(|(path, contents)| *path == key && !contents.trim().is_empty())
+        .map(|(_, contents)| *contents)
+}
+
+/// Reads the input of a puzzle. Fails if it does not exist or is empty, as `scaffold` creates an
+/// empty input file before it is downloaded.
+pub fn read_input(puzzle: Puzzle) -> Result<String, Error> {
+    let input = read("inputs", puzzle, ".txt")?;
+
+    if input.trim().is_empty() {
+        return Err(Error::Empty(puzzle.data_path("inputs", ".txt").into()));
+    }
+
+    Ok(input)
+}
+
+/// Reads the input of a puzzle. If it is missing or empty, offers to download it when attached
+/// to a terminal, and exits otherwise.
+pub fn read_or_download(puzzle: Puzzle) -> String {
+    let e = match read_input(puzzle) {
+        Ok(input) => return input,
+        Err(e @ Error::IO(..)) => {
+            eprintln!("Failed to read input: {e}");
+            process::exit(1);
+        }
+        Err(e) => e,
+    };
+
+    eprintln!("The input of {puzzle} is missing: {e}.");
+
+    if !io::stdin().is_terminal() || !confirm("Download it now?") {
+        eprintln!("Run `{}` to download it.", download_command(puzzle));
+        process::exit(1);
+    }
+
+    if let Err(e) = aoc_client::download(puzzle) {
+        eprintln!("Failed to download input: {e}");
+        process::exit(1);
+    }
+
+    read_input(puzzle).unwrap_or_else(|e| {
+        eprintln!("Failed to read input: {e}");
+        process::exit(1);
+    })
+}
+
+/// Where a solution binary reads its input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Source {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// Any file, given with `--input <path>`.
+    File(PathBuf),
+    /// The example in `data/examples`, given with `--example`.
+    Example,
+    /// A numbered example like `data/examples/10-3.txt`, given with `--example-part <n>`.
+    ExamplePart(u8),
+    /// The standard input, given with `--stdin`.
+    Stdin,
+}
+
+impl Source {
+    /// Reads the source from the arguments passed to the current process.
+    pub fn from_args() -> Self {
+        if let Some(path) = get_arg::<PathBuf>("--input") {
+            Source::File(path)
+        } else if let Some(part) = get_arg("--example-part") {
+            Source::ExamplePart(part)
+        } else if env::args().any(|x| x == "--example") {
+            Source::Example
+        } else if env::args().any(|x| x == "--stdin") {
+            Source::Stdin
+        } else {
+            Source::Puzzle
+        }
+    }
+
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Source::Puzzle => vec![],
+            Source::File(path) => vec!["--input".into(), path.display().to_string()],
+            Source::Example => vec!["--example".into()],
+            Source::ExamplePart(part) => vec!["--example-part".into(), part.to_string()],
+            Source::Stdin => vec!["--stdin".into()],
+        }
+    }
+
+    /// Reads the input of `puzzle` from this source. Exits the process if it cannot be read.
+    pub fn read(&self, puzzle: Puzzle) -> String {
+        let result = match self {
+            Source::Puzzle => return read_or_download(puzzle),
+            Source::File(path) => read_path(path.clone()),
+            Source::Example => read("examples", puzzle, ".txt"),
+            Source::ExamplePart(part) => read("examples", puzzle, &format!("-{part}.txt")),
+            Source::Stdin => read_stdin(),
+        };
+
+        result.unwrap_or_else(|e| {
+            eprintln!("Failed to read input: {e}");
+            process::exit(1);
+        })
+    }
+}
+
+fn read_stdin() -> Result<String, Error> {
+    let path = PathBuf::from("<stdin>");
+    let mut input = String::new();
+
+    match io::stdin().read_to_string(&mut input) {
+        Ok(_) if input.trim().is_empty() => Err(Error::Empty(path)),
+        Ok(_) => Ok(input),
+        Err(e) => Err(Error::IO(path, e)),
+    }
+}
+
+/// The command that downloads the input of a puzzle.
+pub fn download_command(puzzle: Puzzle) -> String {
+    if puzzle.is_scoped() {
+        format!("cargo download {} --year {}", puzzle.day, puzzle.year)
+    } else {
+        format!("cargo download {}", puzzle.day)
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    eprint!("{question} [y/N] ");
+    let _ = io::stderr().flush();
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read, Error};
+    use crate::day;
+
+    #[test]
+    fn reads_examples() {
+        assert!(read("examples", day!(2).into(), ".txt").is_ok());
+    }
+
+    #[test]
+    fn names_missing_path() {
+        let e = read("examples", day!(25).into(), "-missing.txt").unwrap_err();
+        assert!(matches!(e, Error::Missing(_)));
+        assert!(e.to_string().contains("25-missing.txt"));
+    }
+}
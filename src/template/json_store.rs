@@ -0,0 +1,81 @@
+/// Loads and saves the pretty-printed JSON files that the template keeps in `data/`, e.g. the
+/// stored answers, the submission history and the benchmark history.
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+#[derive(Debug)]
+pub enum Error {
+    IO(PathBuf, io::Error),
+    Parser(PathBuf, serde_json::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(path, e) => write!(f, "could not access {}: {e}", path.display()),
+            Error::Parser(path, e) => write!(f, "could not parse {}: {e}", path.display()),
+        }
+    }
+}
+
+/// Loads the contents of a file. Returns the default value if it does not exist.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T, Error> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let json = fs::read_to_string(path).map_err(|e| Error::IO(path.into(), e))?;
+    serde_json::from_str(&json).map_err(|e| Error::Parser(path.into(), e))
+}
+
+/// Replaces the contents of a file, creating its folder if needed.
+pub fn save<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), Error> {
+    let mut json =
+        serde_json::to_string_pretty(value).map_err(|e| Error::Parser(path.into(), e))?;
+    json.push('\n');
+    write(path, &json)
+}
+
+/// Writes a file that belongs to the store, e.g. the input next to an example's answers.
+pub fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::IO(path.into(), e))?;
+    }
+
+    fs::write(path, contents).map_err(|e| Error::IO(path.into(), e))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, save, Error};
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    #[test]
+    fn roundtrips_and_defaults() {
+        let dir = std::env::temp_dir().join(format!("aoc-json-store-{}", std::process::id()));
+        let path = dir.join("nested").join("store.json");
+
+        assert_eq!(
+            load::<BTreeMap<String, u8>>(&path).unwrap(),
+            BTreeMap::new()
+        );
+
+        let value = BTreeMap::from([("a".to_string(), 1_u8)]);
+        save(&path, &value).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\n  \"a\": 1\n}\n");
+        assert_eq!(load::<BTreeMap<String, u8>>(&path).unwrap(), value);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            load::<BTreeMap<String, u8>>(&path),
+            Err(Error::Parser(..))
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
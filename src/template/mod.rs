@@ -1,65 +1,221 @@
-use std::{env, fs};
-
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_history;
+pub mod charts;
 pub mod commands;
+pub mod config;
+pub mod dates;
 mod day;
+mod day_set;
+pub mod examples;
+pub mod fingerprints;
+pub mod input;
+pub mod json_store;
+pub mod params;
+pub mod progress;
+mod puzzle;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
 pub mod runner;
+mod solution;
+pub mod stats;
+pub mod submissions;
 
 pub use day::*;
+pub use day_set::*;
+pub use puzzle::*;
+pub use solution::*;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Accepts a [`Day`] of the configured year or a [`Puzzle`] of any year.
+///
+/// # Panics
+///
+/// Panics with the path of the file if it is missing, see [`input::read`]. An empty file, e.g. an
+/// example that `scaffold` created, is returned as is.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
+    input::read(folder, puzzle.into(), ".txt").unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    input::read(folder, puzzle.into(), &format!("-{part}.txt")).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads the expected answer of a part for the example `NN-k.txt`, as
+/// extracted from the puzzle description by `cargo examples`. E.g. like `01-2.json`.
+///
+/// # Panics
+///
+/// Panics if no answer was recorded for the part or it cannot be parsed as `T`.
+#[must_use]
+pub fn read_example_answer<T: std::str::FromStr>(
+    puzzle: impl Into<Puzzle>,
+    example: u8,
+    part: u8,
+) -> T {
+    let puzzle = puzzle.into();
+    let path = examples::get_path(puzzle, example, ".json");
+
+    let answers = examples::load_answers(puzzle, example).unwrap_or_else(|e| panic!("{e}"));
+    let answer = answers
+        .get(part)
+        .unwrap_or_else(|| panic!("{path} has no answer for part {part}"));
+
+    answer
+        .parse()
+        .unwrap_or_else(|_| panic!("{path}: could not parse the answer of part {part}: {answer}"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `parse` enables a separate parse stage: the day provides a `parse(&str) -> Parsed`
+/// function, which is timed on its own and whose output is passed to both parts as `&Parsed`.
+/// E.g. `solution!(10, parse)` or `solution!(10, parse, 1)`.
+///
+/// Implementations of [`Solution`] are registered with `impl`, e.g. `solution!(impl Day05)`.
+/// Free functions are registered through a hidden implementation, which holds the functions.
+///
+/// The year of the solution is derived from its file name, see [`Puzzle::from_bin_path`].
 #[macro_export]
 macro_rules! solution {
+    (impl $solution:expr) => {
+        $crate::solution!(@register $solution);
+
+        /// The current day.
+        #[allow(dead_code)]
+        const DAY: $crate::template::Day = SOLUTION.day();
+    };
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, part_two], [1, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, $crate::template::unsolved], [1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [$crate::template::unsolved, part_two], [2]);
+    };
+    ($day:expr, parse) => {
+        $crate::solution!(@impl $day, parse, [part_one, part_two], [1, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@impl $day, parse, [part_one, $crate::template::unsolved], [1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@impl $day, parse, [$crate::template::unsolved, part_two], [2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $day:expr, parse, [$part_one:expr, $part_two:expr], [$($part:expr),*]) => {
+        $crate::solution!(@common $day);
+
+        /// Runs the free functions of the current day as a `Solution`.
+        #[doc(hidden)]
+        pub struct __Solution<P, F1, F2, A1, A2>(P, F1, F2, std::marker::PhantomData<fn() -> (A1, A2)>);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        impl<P, F1, F2, A1, A2> $crate::template::Solution for __Solution<P, F1, F2, A1, A2>
+        where
+            P: for<'a> $crate::template::ParseFn<'a> + Sync,
+            F1: for<'a> Fn(&<P as $crate::template::ParseFn<'a>>::Output) -> Option<A1> + Sync,
+            F2: for<'a> Fn(&<P as $crate::template::ParseFn<'a>>::Output) -> Option<A2> + Sync,
+            A1: std::fmt::Display,
+            A2: std::fmt::Display,
+        {
+            const DAY: $crate::template::Day = DAY;
+            const PARTS: &'static [u8] = &[$($part),*];
 
+            type Parsed<'a> = <P as $crate::template::ParseFn<'a>>::Output;
+            type Answer1 = A1;
+            type Answer2 = A2;
+
+            fn parse<'a>(&self, input: &'a str) -> Self::Parsed<'a> {
+                self.0.call(input)
+            }
+
+            fn part_one(&self, input: &Self::Parsed<'_>) -> Option<A1> {
+                (self.1)(input)
+            }
+
+            fn part_two(&self, input: &Self::Parsed<'_>) -> Option<A2> {
+                (self.2)(input)
+            }
+        }
+
+        $crate::solution!(@register __Solution(parse, $part_one, $part_two, std::marker::PhantomData));
+    };
+    (@impl $day:expr, [$part_one:expr, $part_two:expr], [$($part:expr),*]) => {
+        $crate::solution!(@common $day);
+
+        /// Runs the free functions of the current day as a `Solution`.
+        #[doc(hidden)]
+        pub struct __Solution<F1, F2, A1, A2>(F1, F2, std::marker::PhantomData<fn() -> (A1, A2)>);
+
+        impl<F1, F2, A1, A2> $crate::template::Solution for __Solution<F1, F2, A1, A2>
+        where
+            F1: Fn(&str) -> Option<A1> + Sync,
+            F2: Fn(&str) -> Option<A2> + Sync,
+            A1: std::fmt::Display,
+            A2: std::fmt::Display,
+        {
+            const DAY: $crate::template::Day = DAY;
+            const PARSE_STAGE: bool = false;
+            const PARTS: &'static [u8] = &[$($part),*];
+
+            type Parsed<'a> = &'a str;
+            type Answer1 = A1;
+            type Answer2 = A2;
+
+            fn parse<'a>(&self, input: &'a str) -> &'a str {
+                input
+            }
+
+            fn part_one(&self, input: &&str) -> Option<A1> {
+                (self.0)(input)
+            }
+
+            fn part_two(&self, input: &&str) -> Option<A2> {
+                (self.1)(input)
+            }
+        }
+
+        $crate::solution!(@register __Solution($part_one, $part_two, std::marker::PhantomData));
+    };
+
+    (@register $solution:expr) => {
+        /// The solution of the current day, as registered for in-process runs.
+        pub const SOLUTION: $crate::template::registry::Entry =
+            $crate::template::registry::Entry::of(&$solution, file!());
+
+        $crate::solution!(@main);
+    };
+    (@common $day:expr) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+    };
+    (@main) => {
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let puzzle = SOLUTION.puzzle();
+            let input = $crate::template::input::Source::from_args().read(puzzle);
+            let options = RunOptions::from_args();
+            let mut is_failed = false;
+            SOLUTION.run_isolated(input, &options, &mut |report| {
+                is_failed |= report.status == $crate::template::report::Status::Failed;
+                handle_report(report, puzzle, &options)
+            });
+            $crate::template::params::warn_unused();
+            if is_failed {
+                std::process::exit(1);
+            }
         }
     };
 }
@@ -0,0 +1,159 @@
+/// Named parameters of solutions, e.g. the expansion factor of day 11.
+///
+/// A solution declares a parameter by reading it with [`param`], which returns its default unless
+/// it was overridden with `--param <key>=<value>`. This allows to run variants of a puzzle without
+/// editing code, e.g. `cargo solve 11 --param expansion=10`.
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::process;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+/// A `--param` argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub key: String,
+    pub value: String,
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+impl FromStr for Param {
+    type Err = ParamFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => Ok(Self {
+                key: key.trim().into(),
+                value: value.trim().into(),
+            }),
+            _ => Err(ParamFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Param`].
+#[derive(Debug)]
+pub struct ParamFromStrError;
+
+impl Error for ParamFromStrError {}
+
+impl Display for ParamFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a parameter of the form key=value")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The overrides passed to the current process, keyed by parameter name.
+fn overrides() -> &'static BTreeMap<String, String> {
+    static OVERRIDES: OnceLock<BTreeMap<String, String>> = OnceLock::new();
+
+    OVERRIDES.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+
+        args.windows(2)
+            .filter(|x| x[0] == "--param")
+            .filter_map(|x| x[1].parse::<Param>().ok())
+            .map(|x| (x.key, x.value))
+            .collect()
+    })
+}
+
+/// The names of the parameters that the solution has read so far.
+fn declared() -> &'static Mutex<BTreeSet<String>> {
+    static DECLARED: OnceLock<Mutex<BTreeSet<String>>> = OnceLock::new();
+    DECLARED.get_or_init(Default::default)
+}
+
+/// Reads the parameter `name`, or returns `default` if it was not overridden.
+/// Exits the process if the override is not a valid value.
+///
+/// Solutions may call this inside a timed part, so it does not lock anything without overrides.
+pub fn param<T: FromStr>(name: &str, default: T) -> T {
+    let overrides = overrides();
+
+    // without overrides, none can be unused, so there is no need to record the name.
+    if overrides.is_empty() {
+        return default;
+    }
+
+    declare(name);
+
+    match overrides.get(name) {
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            eprintln!("Invalid value for parameter `{name}`: {value}");
+            process::exit(1);
+        }),
+        None => default,
+    }
+}
+
+/// Records that the solution reads `name`. Each thread locks [`declared`] once per name only.
+fn declare(name: &str) {
+    thread_local! {
+        static DECLARED_HERE: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+    }
+
+    let is_new = DECLARED_HERE.with_borrow_mut(|x| !x.contains(name) && x.insert(name.into()));
+
+    if is_new {
+        if let Ok(mut declared) = declared().lock() {
+            declared.insert(name.into());
+        }
+    }
+}
+
+/// Reads the overrides of the process ahead of time, so the first [`param`] does not allocate
+/// inside a timed part.
+pub fn load() {
+    overrides();
+}
+
+/// Whether any parameter was overridden, i.e. the answers may not be the ones of the puzzle.
+pub fn is_overridden() -> bool {
+    !overrides().is_empty()
+}
+
+/// Warns about overrides of parameters that the solution never read, e.g. because of a typo.
+pub fn warn_unused() {
+    let declared = declared().lock().map(|x| x.clone()).unwrap_or_default();
+
+    for key in overrides().keys().filter(|x| !declared.contains(*x)) {
+        let known = if declared.is_empty() {
+            "none".to_string()
+        } else {
+            declared.iter().cloned().collect::<Vec<_>>().join(", ")
+        };
+        eprintln!("Warning: unknown parameter `{key}`, the solution reads: {known}.");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{param, Param};
+
+    #[test]
+    fn parses_params() {
+        let p: Param = "expansion=10".parse().unwrap();
+        assert_eq!((p.key.as_str(), p.value.as_str()), ("expansion", "10"));
+        assert_eq!(p.to_string(), "expansion=10");
+        assert!("expansion".parse::<Param>().is_err());
+        assert!("=10".parse::<Param>().is_err());
+    }
+
+    #[test]
+    fn defaults_without_override() {
+        assert_eq!(param("expansion", 2_usize), 2);
+    }
+}
@@ -0,0 +1,191 @@
+/// Tracks the ⭐️ progress of a year from the data that is stored locally, without contacting
+/// Advent of Code: a part counts as solved if its answer was recorded in `data/answers/` or
+/// accepted according to `data/submissions/`.
+use std::path::Path;
+
+use crate::template::aoc_client::Verdict;
+use crate::template::dates::Date;
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::{all_days, answers, submissions, Day, Puzzle, Year};
+
+/// The solved parts of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+    /// Whether the day has a solution file, which the calendar links to.
+    pub has_solution: bool,
+}
+
+impl Stars {
+    pub fn count(&self) -> u8 {
+        u8::from(self.part_1) + u8::from(self.part_2)
+    }
+}
+
+/// The stars of every day of a year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub year: Year,
+    pub days: Vec<Stars>,
+}
+
+impl Progress {
+    pub fn total(&self) -> u32 {
+        self.days.iter().map(|x| u32::from(x.count())).sum()
+    }
+}
+
+/// Collects the progress of a year. Unreadable data files are reported and count as unsolved.
+pub fn load(year: Year) -> Progress {
+    let mut days: Vec<Stars> = all_days()
+        .map(|day| {
+            let puzzle = Puzzle::new(year, day);
+
+            let answers = answers::load(puzzle).unwrap_or_else(|e| {
+                eprintln!("Day {day}: {e}");
+                answers::Answers::default()
+            });
+
+            let history = submissions::load(puzzle).unwrap_or_else(|e| {
+                eprintln!("Day {day}: {e}");
+                submissions::History::default()
+            });
+
+            let is_solved = |part: u8| {
+                answers.get(part).is_some()
+                    || history
+                        .for_part(part)
+                        .any(|x| x.verdict == Verdict::Correct)
+            };
+
+            Stars {
+                day,
+                part_1: is_solved(1),
+                part_2: is_solved(2),
+                has_solution: Path::new(&puzzle.bin_path()).exists(),
+            }
+        })
+        .collect();
+
+    award_last_star(&mut days);
+
+    Progress { year, days }
+}
+
+/// The second part of day 25 has no puzzle, its star is awarded once all other stars are collected.
+fn award_last_star(days: &mut [Stars]) {
+    let stars = days.iter().map(|x| u32::from(x.count())).sum::<u32>();
+
+    if let Some(last) = days.iter_mut().find(|x| x.day == 25) {
+        if !last.part_2 && last.part_1 && stars == 49 {
+            last.part_2 = true;
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the progress as a calendar of December with a column per weekday, starting on Monday.
+pub fn construct_calendar(prefix: &str, progress: &Progress) -> String {
+    const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+    let mut lines = vec![
+        format!("{prefix} ⭐️ Progress {}", progress.year),
+        String::new(),
+        format!("| {} |", WEEKDAYS.join(" | ")),
+        format!("|{} |", " :---: |".repeat(6) + " :---: "),
+    ];
+
+    let offset = Date {
+        year: progress.year.into_inner().into(),
+        month: 12,
+        day: 1,
+    }
+    .weekday();
+    let mut cells: Vec<String> = vec![String::new(); offset];
+    cells.extend(progress.days.iter().map(format_cell));
+
+    for week in cells.chunks(7) {
+        let row: String = (0..7)
+            .map(|i| match week.get(i) {
+                Some(cell) if !cell.is_empty() => format!(" {cell} |"),
+                _ => " |".into(),
+            })
+            .collect();
+        lines.push(format!("|{row}"));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {} ⭐️**", progress.total()));
+
+    lines.join("\n")
+}
+
+fn format_cell(stars: &Stars) -> String {
+    let day = stars.day.into_inner();
+
+    let label = if stars.has_solution {
+        format!("[{day}]({})", get_path_for_bin(stars.day))
+    } else {
+        day.to_string()
+    };
+
+    match stars.count() {
+        0 => label,
+        count => format!("{label}<br>{}", "⭐️".repeat(count.into())),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{award_last_star, construct_calendar, Progress, Stars};
+    use crate::template::{all_days, Year};
+
+    fn get_mock_progress(stars: &[(u8, u8)]) -> Progress {
+        let year = Year::new(2023).unwrap();
+        let days = all_days()
+            .map(|day| {
+                let count = stars.iter().find(|x| day == x.0).map_or(0, |x| x.1);
+                Stars {
+                    day,
+                    part_1: count >= 1,
+                    part_2: count >= 2,
+                    has_solution: count > 0,
+                }
+            })
+            .collect();
+        Progress { year, days }
+    }
+
+    #[test]
+    fn awards_last_star() {
+        let mut progress = get_mock_progress(&[(25, 1)]);
+        award_last_star(&mut progress.days);
+        assert_eq!(progress.total(), 1);
+
+        let all: Vec<(u8, u8)> = (1..=24).map(|x| (x, 2)).chain([(25, 1)]).collect();
+        let mut progress = get_mock_progress(&all);
+        award_last_star(&mut progress.days);
+        assert_eq!(progress.total(), 50);
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let calendar = construct_calendar("##", &get_mock_progress(&[(1, 2), (2, 1)]));
+        let lines: Vec<&str> = calendar.lines().collect();
+
+        assert_eq!(lines[0], "## ⭐️ Progress 2023");
+        assert_eq!(lines[2], "| Mon | Tue | Wed | Thu | Fri | Sat | Sun |");
+        assert_eq!(
+            lines[4],
+            "| | | | | [1](./src/bin/01.rs)<br>⭐️⭐️ | [2](./src/bin/02.rs)<br>⭐️ | 3 |"
+        );
+        assert_eq!(lines[5], "| 4 | 5 | 6 | 7 | 8 | 9 | 10 |");
+        assert_eq!(lines[8], "| 25 | | | | | | |");
+        assert_eq!(lines[10], "**Total: 3 ⭐️**");
+    }
+}
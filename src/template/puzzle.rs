@@ -0,0 +1,230 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::template::dates::Date;
+use crate::template::{config, Day};
+
+/// The year of an Advent of Code event, i.e. 2015 or later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] if an event took place in it, returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        (year >= 2015).then_some(Self(year))
+    }
+
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year that commands default to: `AOC_YEAR` if set, or else the year in `aoc.toml`,
+    /// or else the latest event.
+    pub fn configured() -> Self {
+        env::var("AOC_YEAR")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .or(config::get().year)
+            .unwrap_or_else(Self::latest)
+    }
+
+    /// The year of the latest event that has started, based on the system clock.
+    pub fn latest() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::latest_at(secs)
+    }
+
+    fn latest_at(secs: u64) -> Self {
+        let date = Date::from_timestamp(secs);
+        let year = if date.month == 12 {
+            date.year
+        } else {
+            date.year - 1
+        };
+
+        Self(u16::try_from(year).map_or(2015, |x| x.max(2015)))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+impl Serialize for Year {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Year {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let year = u16::deserialize(deserializer)?;
+        Self::new(year).ok_or_else(|| de::Error::custom(YearFromStrError))
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A puzzle, identified by the year of its event and its day.
+///
+/// Puzzles of the [configured year](Year::configured) use the flat layout, e.g. `src/bin/05.rs` and
+/// `data/inputs/05.txt`. Puzzles of any other year are scoped by year, e.g. `src/bin/2022-05.rs`
+/// and `data/2022/inputs/05.txt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Identifies the puzzle of a solution from the path of its source file:
+    /// `src/bin/2022-05.rs` belongs to 2022, `src/bin/05.rs` to the configured year.
+    pub fn from_bin_path(path: &str, day: Day) -> Self {
+        let year = path
+            .rsplit(['/', '\\'])
+            .next()
+            .and_then(|name| name.split_once('-'))
+            .and_then(|(year, _)| year.parse().ok())
+            .unwrap_or_else(Year::configured);
+
+        Self::new(year, day)
+    }
+
+    /// Whether the puzzle uses the year-scoped layout.
+    pub fn is_scoped(self) -> bool {
+        self.year != Year::configured()
+    }
+
+    /// The name of the puzzle's solution binary, e.g. `05` or `2022-05`.
+    pub fn bin_name(self) -> String {
+        if self.is_scoped() {
+            format!("{}-{}", self.year, self.day)
+        } else {
+            self.day.to_string()
+        }
+    }
+
+    pub fn bin_path(self) -> String {
+        let path = Path::new("src/bin").join(format!("{}.rs", self.bin_name()));
+        config::get().resolve(path).display().to_string()
+    }
+
+    /// The path of a data file of this puzzle, e.g. `data/inputs/05.txt` for `("inputs", ".txt")`.
+    /// The folders can be configured in `aoc.toml`.
+    pub fn data_path(self, folder: &str, suffix: &str) -> String {
+        config::get()
+            .resolve(self.data_file(folder, suffix))
+            .display()
+            .to_string()
+    }
+
+    /// The path of a data file of this puzzle, relative to the project root.
+    pub fn data_file(self, folder: &str, suffix: &str) -> PathBuf {
+        let mut dir = config::get().paths.folder(folder);
+
+        if self.is_scoped() {
+            let year = self.year.to_string();
+            dir = match (dir.parent(), dir.file_name()) {
+                (Some(parent), Some(name)) => parent.join(year).join(name),
+                _ => dir.join(year),
+            };
+        }
+
+        dir.join(format!("{}{suffix}", self.day))
+    }
+}
+
+/// The puzzle of this day in the configured year.
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self::new(Year::configured(), day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Puzzle, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2022".parse::<Year>().unwrap().into_inner(), 2022);
+        assert!("2014".parse::<Year>().is_err());
+        assert!("22".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn finds_latest_event() {
+        // 2023-11-30 23:59:59 and 2023-12-01 00:00:00 UTC
+        assert_eq!(Year::latest_at(1_701_388_799).into_inner(), 2022);
+        assert_eq!(Year::latest_at(1_701_388_800).into_inner(), 2023);
+        // 2024-12-01, a leap year
+        assert_eq!(Year::latest_at(1_733_011_200).into_inner(), 2024);
+    }
+
+    #[test]
+    fn identifies_puzzles_by_bin_path() {
+        let year = Year::new(2016).unwrap();
+        let puzzle = Puzzle::from_bin_path("/repo/src/bin/2016-05.rs", day!(5));
+        assert_eq!(puzzle, Puzzle::new(year, day!(5)));
+        assert_eq!(puzzle.to_string(), "2016-05");
+
+        let puzzle = Puzzle::from_bin_path("src/bin/05.rs", day!(5));
+        assert_eq!(puzzle.year, Year::configured());
+        assert_eq!(puzzle.bin_path(), "src/bin/05.rs");
+        assert_eq!(puzzle.data_path("inputs", ".txt"), "data/inputs/05.txt");
+    }
+
+    #[test]
+    fn scopes_other_years() {
+        let puzzle = Puzzle::new(Year::new(2016).unwrap(), day!(5));
+        assert_eq!(puzzle.bin_path(), "src/bin/2016-05.rs");
+        assert_eq!(
+            puzzle.data_path("answers", ".json"),
+            "data/2016/answers/05.json"
+        );
+    }
+}
@@ -1,9 +1,18 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
 use std::{fs, io};
 
-use crate::template::Day;
+use serde::Deserialize;
 
+use crate::template::alloc::{format_bytes, Allocations};
+use crate::template::config::{self, Readme};
+use crate::template::stats::{format_duration, Statistic, Summary};
+use crate::template::{charts::Image, Day};
+
+#[cfg(feature = "test_lib")]
 static MARKER: &str = "<!--- benchmarking table --->";
 
 #[derive(Debug)]
@@ -18,14 +27,274 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => f.write_str(e),
+            Error::IO(e) => write!(f, "could not access readme: {e}"),
+        }
+    }
+}
+
+/// The outcome of a stage of a day, as shown in the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Timed {
+        duration: Summary,
+        samples: usize,
+    },
+    /// The part returned `None`, or the day could not be run.
+    Unsolved,
+    /// The stage panicked or timed out.
+    Failed,
+}
+
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<Outcome>,
+    pub part_1: Option<Outcome>,
+    pub part_2: Option<Outcome>,
     pub total_nanos: f64,
+    /// The allocations of all stages, with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+}
+
+impl Timings {
+    /// A day that has a solution, but no timings, e.g. because its input is missing.
+    pub fn unsolved(day: Day) -> Self {
+        Self {
+            day,
+            parse: None,
+            part_1: Some(Outcome::Unsolved),
+            part_2: Some(Outcome::Unsolved),
+            total_nanos: 0_f64,
+            allocations: None,
+        }
+    }
+
+    /// The outcome of a stage, `None` being the parse stage.
+    pub fn stage(&self, part: Option<u8>) -> Option<&Outcome> {
+        match part {
+            None => self.parse.as_ref(),
+            Some(1) => self.part_1.as_ref(),
+            Some(2) => self.part_2.as_ref(),
+            Some(_) => None,
+        }
+    }
+
+    fn is_timed(&self) -> bool {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .any(|x| matches!(x, Some(Outcome::Timed { .. })))
+    }
 }
 
+/* -------------------------------------------------------------------------- */
+
+/// A column of the benchmark table, configured with `columns` in the `[readme]` table of `aoc.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Column {
+    /// The time of a stage, `None` being the parse stage. Uses the statistic of `cargo time --stat`
+    /// unless a fixed one is given, e.g. `part_1` or `part_1_median`.
+    Time {
+        part: Option<u8>,
+        statistic: Option<Statistic>,
+    },
+    /// The number of samples of a stage, e.g. `part_2_samples`.
+    Samples {
+        part: Option<u8>,
+    },
+    /// The time of all stages of a day.
+    Total,
+    Allocations,
+    Allocated,
+    Peak,
+}
+
+impl Column {
+    /// The columns of the table if none are configured. The parse and allocation columns are only
+    /// shown if a day has a value for them.
+    pub fn defaults() -> Vec<Column> {
+        vec![
+            Column::Time {
+                part: None,
+                statistic: None,
+            },
+            Column::Time {
+                part: Some(1),
+                statistic: None,
+            },
+            Column::Time {
+                part: Some(2),
+                statistic: None,
+            },
+            Column::Allocations,
+            Column::Allocated,
+            Column::Peak,
+        ]
+    }
+
+    fn header(self) -> String {
+        let stage = |part: Option<u8>| match part {
+            Some(part) => format!("Part {part}"),
+            None => "Parse".into(),
+        };
+
+        match self {
+            Column::Time {
+                part,
+                statistic: None,
+            } => stage(part),
+            Column::Time {
+                part,
+                statistic: Some(statistic),
+            } => format!("{} ({statistic})", stage(part)),
+            Column::Samples { part } => format!("{} (samples)", stage(part)),
+            Column::Total => "Total".into(),
+            Column::Allocations => "Allocations".into(),
+            Column::Allocated => "Allocated".into(),
+            Column::Peak => "Peak".into(),
+        }
+    }
+
+    /// The cell of a day, or `None` if there is no value to show.
+    fn cell(self, timing: &Timings, statistic: Statistic) -> Option<String> {
+        match self {
+            Column::Time {
+                part,
+                statistic: fixed,
+            } => match timing.stage(part)? {
+                Outcome::Timed { duration, .. } => Some(format!(
+                    "`{}`",
+                    format_duration(duration.get(fixed.unwrap_or(statistic)))
+                )),
+                Outcome::Unsolved => Some("_unsolved_".into()),
+                Outcome::Failed => Some("**failed**".into()),
+            },
+            Column::Samples { part } => match timing.stage(part)? {
+                Outcome::Timed { samples, .. } => Some(samples.to_string()),
+                _ => None,
+            },
+            Column::Total => timing.is_timed().then(|| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let nanos = timing.total_nanos.round() as u64;
+                format!("`{}`", format_duration(Duration::from_nanos(nanos)))
+            }),
+            Column::Allocations => timing.allocations.map(|x| x.count.to_string()),
+            Column::Allocated => timing.allocations.map(|x| format_bytes(x.bytes)),
+            Column::Peak => timing.allocations.map(|x| format_bytes(x.peak_bytes)),
+        }
+    }
+
+    /// Whether the column is left out if no day has a value for it.
+    fn is_optional(self) -> bool {
+        !matches!(self, Column::Time { part: Some(_), .. })
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stage = |part: Option<u8>| match part {
+            Some(part) => format!("part_{part}"),
+            None => "parse".into(),
+        };
+
+        match *self {
+            Column::Time {
+                part,
+                statistic: None,
+            } => f.write_str(&stage(part)),
+            Column::Time {
+                part,
+                statistic: Some(statistic),
+            } => write!(f, "{}_{statistic}", stage(part)),
+            Column::Samples { part } => write!(f, "{}_samples", stage(part)),
+            Column::Total => f.write_str("total"),
+            Column::Allocations => f.write_str("allocations"),
+            Column::Allocated => f.write_str("allocated"),
+            Column::Peak => f.write_str("peak"),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = ColumnFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "total" => return Ok(Column::Total),
+            "allocations" => return Ok(Column::Allocations),
+            "allocated" => return Ok(Column::Allocated),
+            "peak" => return Ok(Column::Peak),
+            _ => {}
+        }
+
+        let (part, measure) = if let Some(rest) = s.strip_prefix("parse") {
+            (None, rest)
+        } else if let Some(rest) = s.strip_prefix("part_1") {
+            (Some(1), rest)
+        } else if let Some(rest) = s.strip_prefix("part_2") {
+            (Some(2), rest)
+        } else {
+            return Err(ColumnFromStrError(s.into()));
+        };
+
+        match measure {
+            "" => Ok(Column::Time {
+                part,
+                statistic: None,
+            }),
+            "_samples" => Ok(Column::Samples { part }),
+            _ => measure
+                .strip_prefix('_')
+                .and_then(|x| x.parse().ok())
+                .map(|statistic| Column::Time {
+                    part,
+                    statistic: Some(statistic),
+                })
+                .ok_or_else(|| ColumnFromStrError(s.into())),
+        }
+    }
+}
+
+impl TryFrom<String> for Column {
+    type Error = ColumnFromStrError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// An error which can be returned when parsing a [`Column`].
+#[derive(Debug)]
+pub struct ColumnFromStrError(String);
+
+impl std::error::Error for ColumnFromStrError {}
+
+impl Display for ColumnFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown column `{}`, expecting parse, part_1 or part_2 optionally followed by _samples or a statistic like _median, or one of: total, allocations, allocated, peak",
+            self.0
+        )
+    }
+}
+
+/// The order of the days in the benchmark table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sort {
+    #[default]
+    Day,
+    /// The slowest day first. Days without timings come last.
+    Slowest,
+}
+
+/* -------------------------------------------------------------------------- */
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -36,8 +305,8 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,74 +327,190 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    readme: &Readme,
+    mut timings: Vec<Timings>,
+    total_millis: f64,
+    statistic: Statistic,
+    images: &[Image],
+) -> String {
+    let header = match statistic {
+        Statistic::Mean => format!("{prefix} Benchmarks"),
+        statistic => format!("{prefix} Benchmarks ({statistic})"),
+    };
+
+    if readme.sort == Sort::Slowest {
+        timings.sort_by(|a, b| {
+            b.is_timed()
+                .cmp(&a.is_timed())
+                .then(b.total_nanos.total_cmp(&a.total_nanos))
+                .then(a.day.cmp(&b.day))
+        });
+    }
+
+    // optional columns, e.g. allocations which are only counted with `count-allocations`,
+    // are left out if no day has a value for them.
+    let columns: Vec<Column> = readme
+        .columns
+        .iter()
+        .copied()
+        .filter(|column| {
+            !column.is_optional() || timings.iter().any(|x| column.cell(x, statistic).is_some())
+        })
+        .collect();
+
+    let mut lines: Vec<String> = vec![header, String::new()];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let headers: Vec<String> = ["Day".to_string()]
+        .into_iter()
+        .chain(columns.iter().map(|x| x.header()))
+        .collect();
 
-    for timing in timings {
+    lines.push(format!("| {} |", headers.join(" | ")));
+    lines.push(format!(
+        "|{} |",
+        " :---: |".repeat(headers.len() - 1) + " :---: "
+    ));
+
+    for timing in &timings {
         let path = get_path_for_bin(timing.day);
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                column
+                    .cell(timing, statistic)
+                    .unwrap_or_else(|| match column {
+                        Column::Time { .. } | Column::Total => "`-`".into(),
+                        _ => "-".into(),
+                    })
+            })
+            .collect();
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cells.join(" | ")
         ));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!(
+        "**Total: {}**",
+        format_duration(Duration::from_secs_f64(total_millis / 1000.0))
+    ));
+
+    if !images.is_empty() {
+        lines.push(String::new());
+        lines.extend(images.iter().map(|x| format!("![{}]({})", x.alt, x.path)));
+    }
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    readme: &Readme,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    statistic: Statistic,
+    images: &[Image],
+) -> Result<(), Error> {
+    let table = construct_table("##", readme, timings, total_millis, statistic, images);
+    replace_block(s, &readme.marker, &table)
+}
+
+/// Replaces everything between the two occurrences of `marker` in a readme with `content`.
+/// Works for a readme with only a single marker too, which is then followed by the content.
+pub fn replace_block(s: &mut String, marker: &str, content: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &format!("{marker}\n{content}\n{marker}"),
+    );
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+/// Updates the table in the readme. The readme, the marker and the layout of the table can be
+/// configured in `aoc.toml`. `images` are embedded below the table, see
+/// [`charts`](crate::template::charts).
+pub fn update(
+    timings: Vec<Timings>,
+    total_millis: f64,
+    statistic: Statistic,
+    images: &[Image],
+) -> Result<(), Error> {
+    let readme = &config::get().readme;
+    let path = config::get().resolve(&readme.path);
+    let mut contents = String::from_utf8_lossy(&fs::read(&path)?).to_string();
+    update_content(
+        &mut contents,
+        readme,
+        timings,
+        total_millis,
+        statistic,
+        images,
+    )?;
+    fs::write(&path, &contents)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{update_content, Column, Outcome, Sort, Timings, MARKER};
     use crate::day;
+    use crate::template::alloc::Allocations;
+    use crate::template::charts::Image;
+    use crate::template::config::Readme;
+    use crate::template::stats::{Statistic, Stats};
+    use std::time::Duration;
+
+    fn get_readme() -> Readme {
+        Readme {
+            marker: MARKER.into(),
+            ..Readme::default()
+        }
+    }
+
+    fn timed(millis: u64) -> Outcome {
+        let stats = Stats::new(vec![
+            Duration::from_micros(millis * 500),
+            Duration::from_millis(millis),
+            Duration::from_micros(millis * 1500),
+        ]);
+
+        Outcome::Timed {
+            duration: stats.summary(),
+            samples: stats.len(),
+        }
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                parse: None,
+                part_1: Some(timed(10)),
+                part_2: Some(timed(20)),
                 total_nanos: 3e+10,
+                allocations: None,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                parse: None,
+                part_1: Some(timed(30)),
+                part_2: Some(timed(40)),
                 total_nanos: 7e+10,
+                allocations: None,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                parse: None,
+                part_1: Some(timed(40)),
+                part_2: Some(timed(50)),
                 total_nanos: 9e+10,
+                allocations: None,
             },
         ]
     }
@@ -134,28 +519,68 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_readme(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+            &[],
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_readme(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+            &[],
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_readme(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+            &[],
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_readme(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+            &[],
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &get_readme(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+            &[],
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +588,15 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_readme(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+            &[],
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -172,15 +605,192 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 190ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings[1].parse = Some(timed(5));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &get_readme(), timings, 195.0, Statistic::Mean, &[]).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.00ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `50.0ms` |",
+            "",
+            "**Total: 195ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings.truncate(2);
+        timings[0].allocations = Some(Allocations {
+            count: 12,
+            bytes: 2048,
+            peak_bytes: 512,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &get_readme(), timings, 100.0, Statistic::Mean, &[]).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Allocations | Allocated | Peak |",
+            "| :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | 12 | 2.0 KiB | 512 B |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | - | - | - |",
+            "",
+            "**Total: 100ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn embeds_images_below_table() {
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        let images = [Image {
+            alt: "Benchmarks per day".into(),
+            path: "./.assets/benchmarks.svg".into(),
+        }];
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &get_readme(),
+            timings,
+            30.0,
+            Statistic::Mean,
+            &images,
+        )
+        .unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "",
+            "**Total: 30.0ms**",
+            "",
+            "![Benchmarks per day](./.assets/benchmarks.svg)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn shows_unsolved_and_failed_days() {
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        timings[0].part_2 = Some(Outcome::Failed);
+        timings.push(Timings::unsolved(day!(3)));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &get_readme(), timings, 10.0, Statistic::Mean, &[]).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | **failed** |"));
+        assert!(s.contains("| [Day 3](./src/bin/03.rs) | _unsolved_ | _unsolved_ |"));
+    }
+
+    #[test]
+    fn formats_configured_columns() {
+        let readme = Readme {
+            columns: [
+                "part_1",
+                "part_1_median",
+                "part_1_stddev",
+                "part_1_samples",
+                "total",
+            ]
+            .into_iter()
+            .map(|x| x.parse::<Column>().unwrap())
+            .collect(),
+            sort: Sort::Slowest,
+            ..get_readme()
+        };
+
+        let mut timings = get_mock_timings();
+        timings.push(Timings::unsolved(day!(3)));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &readme, timings, 190.0, Statistic::Max, &[]).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks (max)",
+            "",
+            "| Day | Part 1 | Part 1 (median) | Part 1 (stddev) | Part 1 (samples) | Total |",
+            "| :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/04.rs) | `60.0ms` | `40.0ms` | `16.3ms` | 3 | `90.0s` |",
+            "| [Day 2](./src/bin/02.rs) | `45.0ms` | `30.0ms` | `12.2ms` | 3 | `70.0s` |",
+            "| [Day 1](./src/bin/01.rs) | `15.0ms` | `10.0ms` | `4.08ms` | 3 | `30.0s` |",
+            "| [Day 3](./src/bin/03.rs) | _unsolved_ | _unsolved_ | _unsolved_ | - | `-` |",
+            "",
+            "**Total: 190ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn parses_columns() {
+        for column in [
+            "parse",
+            "part_2",
+            "part_2_p95",
+            "parse_samples",
+            "total",
+            "peak",
+        ] {
+            assert_eq!(column.parse::<Column>().unwrap().to_string(), column);
+        }
+        assert_eq!(
+            "part_1_MEDIAN".parse::<Column>().unwrap().to_string(),
+            "part_1_median"
+        );
+        assert!("part_3".parse::<Column>().is_err());
+        assert!("part_1_average".parse::<Column>().is_err());
+        assert!("part_1samples".parse::<Column>().is_err());
+    }
+
+    #[test]
+    fn labels_non_default_statistic() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &get_readme(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Median,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(s.contains("## Benchmarks (median)"), true);
+    }
 }
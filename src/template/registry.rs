@@ -0,0 +1,200 @@
+/// Solutions that are compiled into the main binary, so any set of days can be run in-process.
+/// Every `solution!` invocation defines a `SOLUTION` constant, which `build.rs` collects into a list.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::template::report::{PartReport, Status};
+use crate::template::runner::{self, RunOptions};
+use crate::template::{solution, Day, Puzzle, Solution};
+
+/// A [`Solution`] whose types are erased, so the solutions of all days fit in one list.
+trait Run: Sync {
+    fn run(
+        &self,
+        puzzle: Puzzle,
+        input: &str,
+        options: &RunOptions,
+        on_report: &mut dyn FnMut(&PartReport),
+    );
+}
+
+impl<S: Solution + Sync> Run for S {
+    fn run(
+        &self,
+        puzzle: Puzzle,
+        input: &str,
+        options: &RunOptions,
+        on_report: &mut dyn FnMut(&PartReport),
+    ) {
+        solution::run(self, puzzle, input, options, on_report);
+    }
+}
+
+/// The number of threads that [`Entry::run_isolated`] left running after a stage timed out.
+static ABANDONED_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns how many timed out stages are still running, or ran, in the background of this process.
+/// They compete with later stages for the CPU, which skews their timings.
+pub fn abandoned_threads() -> usize {
+    ABANDONED_THREADS.load(Ordering::Relaxed)
+}
+
+/// A message from the thread of [`Entry::run_isolated`].
+enum Message {
+    /// The first run of the current stage has finished, benching may follow.
+    FirstRun,
+    Report(Box<PartReport>),
+}
+
+/// A registered solution of a day.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    day: Day,
+    /// Path of the source file, which determines the year of the puzzle.
+    path: &'static str,
+    solution: &'static dyn Run,
+    parse_stage: bool,
+    parts: &'static [u8],
+    title: Option<&'static str>,
+}
+
+impl Entry {
+    /// Registers an implementation of [`Solution`].
+    pub const fn of<S: Solution + Sync>(solution: &'static S, path: &'static str) -> Self {
+        Self {
+            day: S::DAY,
+            path,
+            solution,
+            parse_stage: S::PARSE_STAGE,
+            parts: S::PARTS,
+            title: S::TITLE,
+        }
+    }
+
+    pub const fn day(&self) -> Day {
+        self.day
+    }
+
+    pub fn title(&self) -> Option<&'static str> {
+        self.title
+    }
+
+    /// The parts that the solution runs, e.g. `[2]` if it leaves out part one.
+    pub fn parts(&self) -> &'static [u8] {
+        self.parts
+    }
+
+    pub fn puzzle(&self) -> Puzzle {
+        Puzzle::from_bin_path(self.path, self.day)
+    }
+
+    /// Runs the solution on `input`. `on_report` is called as soon as a stage has finished.
+    pub fn run(&self, input: &str, options: &RunOptions, on_report: &mut dyn FnMut(&PartReport)) {
+        self.solution.run(self.puzzle(), input, options, on_report);
+    }
+
+    /// The stages in the order they run: `None` for the parse stage, or the number of a part.
+    fn stages(&self) -> impl Iterator<Item = Option<u8>> {
+        let parse = self.parse_stage.then_some(None);
+        parse.into_iter().chain(self.parts.iter().map(|&x| Some(x)))
+    }
+
+    /// Runs the solution on its own thread, so a stage whose first run takes longer than the
+    /// timeout in `options` can be reported as failed. Warming up and benching with `--time` is
+    /// not limited. The thread of such a stage is left running in the background, as it cannot
+    /// be stopped, see [`abandoned_threads`].
+    pub fn run_isolated(
+        &self,
+        input: String,
+        options: &RunOptions,
+        on_report: &mut dyn FnMut(&PartReport),
+    ) {
+        let (tx, rx) = mpsc::channel();
+        let (solution, run_options) = (*self, *options);
+
+        let spawned = thread::Builder::new()
+            .name(format!("day-{}", self.day))
+            .spawn(move || {
+                let first_run = tx.clone();
+                runner::on_first_run(move || {
+                    let _ = first_run.send(Message::FirstRun);
+                });
+
+                solution.run(&input, &run_options, &mut |report| {
+                    let _ = tx.send(Message::Report(Box::new(report.clone())));
+                });
+            });
+
+        if let Err(e) = spawned {
+            eprintln!("Failed to spawn a thread for day {}: {e}", self.day);
+            return;
+        }
+
+        for stage in self.stages() {
+            let timer = Instant::now();
+
+            match receive_report(&rx, options.timeout) {
+                Ok(report) => {
+                    on_report(&report);
+                    // the parts do not run if the parse stage failed.
+                    if report.status == Status::Failed && report.part.is_none() {
+                        return;
+                    }
+                }
+                Err(Some(timeout)) => {
+                    ABANDONED_THREADS.fetch_add(1, Ordering::Relaxed);
+                    let error = format!("timed out after {timeout:?}");
+                    on_report(&PartReport::failed(
+                        self.puzzle(),
+                        stage,
+                        error,
+                        timer.elapsed(),
+                    ));
+                    return;
+                }
+                // the thread stopped before running all stages, e.g. due to an uncaught panic.
+                Err(None) => {
+                    let error = "stopped unexpectedly".to_string();
+                    on_report(&PartReport::failed(
+                        self.puzzle(),
+                        stage,
+                        error,
+                        timer.elapsed(),
+                    ));
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Finds the solution of a puzzle in a list of registered solutions.
+pub fn find(solutions: &[Entry], puzzle: Puzzle) -> Option<&Entry> {
+    solutions.iter().find(|x| x.puzzle() == puzzle)
+}
+
+/// Waits for the report of the next stage. `timeout` limits the wait for its first run only.
+/// Fails with the timeout if it passed, or with `None` if the thread stopped.
+fn receive_report(
+    rx: &Receiver<Message>,
+    timeout: Option<Duration>,
+) -> Result<PartReport, Option<Duration>> {
+    let mut remaining = timeout;
+
+    loop {
+        let message = match remaining {
+            Some(timeout) => rx.recv_timeout(timeout).map_err(|e| match e {
+                RecvTimeoutError::Timeout => Some(timeout),
+                RecvTimeoutError::Disconnected => None,
+            })?,
+            None => rx.recv().map_err(|_| None)?,
+        };
+
+        match message {
+            Message::FirstRun => remaining = None,
+            Message::Report(report) => return Ok(*report),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+/// Structured records that solution binaries emit when invoked with `--format json`.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::alloc::Allocations;
+use crate::template::stats::{Stats, Summary};
+use crate::template::{Day, Puzzle, Year};
+
+/// Output format of the `solve` and `all` commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable output with colors and progress indicators.
+    #[default]
+    Human,
+    /// One JSON-encoded [`PartReport`] per line.
+    Json,
+}
+
+impl Format {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Format::Human => "human",
+            Format::Json => "json",
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of: human, json")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The stage ran and, for parts, produced an answer.
+    Ok,
+    /// The part returned `None`.
+    Unsolved,
+    /// The stage panicked or timed out, see [`PartReport::error`].
+    Failed,
+}
+
+/// The outcome of running a single stage of a solution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub year: Year,
+    pub day: Day,
+    /// The part number, or `None` for the parse stage.
+    pub part: Option<u8>,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub samples: usize,
+    pub duration: Summary,
+    /// The allocations of a single run, with the `count-allocations` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<Allocations>,
+    /// Why the stage failed, e.g. `panicked: not yet implemented`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PartReport {
+    pub fn new(puzzle: Puzzle, part: Option<u8>, answer: Option<String>, stats: &Stats) -> Self {
+        let status = match (part, &answer) {
+            (Some(_), None) => Status::Unsolved,
+            _ => Status::Ok,
+        };
+
+        Self {
+            year: puzzle.year,
+            day: puzzle.day,
+            part,
+            status,
+            answer,
+            samples: stats.len(),
+            duration: stats.summary(),
+            allocations: None,
+            error: None,
+        }
+    }
+
+    /// A stage that did not finish, after running for `elapsed`.
+    pub fn failed(puzzle: Puzzle, part: Option<u8>, error: String, elapsed: Duration) -> Self {
+        let stats = Stats::new(vec![elapsed]);
+
+        Self {
+            status: Status::Failed,
+            error: Some(error),
+            ..Self::new(puzzle, part, None, &stats)
+        }
+    }
+
+    /// The label of the stage as shown in human-readable output, e.g. `Part 1`.
+    pub fn label(&self) -> String {
+        match self.part {
+            Some(part) => format!("Part {part}"),
+            None => "Parse".into(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("report should be serializable")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Format, PartReport, Status};
+    use crate::day;
+    use crate::template::stats::Stats;
+    use crate::template::{Day, Puzzle, Year};
+    use std::time::Duration;
+
+    fn get_puzzle(day: Day) -> Puzzle {
+        Puzzle::new(Year::new(2022).unwrap(), day)
+    }
+
+    fn from_json(json: &str) -> Option<PartReport> {
+        serde_json::from_str(json).ok()
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::new(vec![Duration::from_nanos(100), Duration::from_nanos(300)]);
+        let report = PartReport::new(get_puzzle(day!(5)), Some(1), Some("35".into()), &stats);
+        let json = report.to_json();
+
+        assert_eq!(
+            json,
+            r#"{"year":2022,"day":5,"part":1,"status":"ok","answer":"35","samples":2,"duration":{"mean_ns":200,"median_ns":200,"min_ns":100,"max_ns":300,"stddev_ns":100,"p95_ns":300,"p99_ns":300}}"#
+        );
+        assert_eq!(from_json(&json), Some(report));
+    }
+
+    #[test]
+    fn marks_missing_answers_as_unsolved() {
+        let stats = Stats::new(vec![Duration::from_nanos(100)]);
+        assert_eq!(
+            PartReport::new(get_puzzle(day!(1)), Some(2), None, &stats).status,
+            Status::Unsolved
+        );
+        assert_eq!(
+            PartReport::new(get_puzzle(day!(1)), None, None, &stats).status,
+            Status::Ok
+        );
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        let report = PartReport::failed(
+            get_puzzle(day!(10)),
+            Some(2),
+            "panicked: not yet implemented".into(),
+            Duration::from_nanos(100),
+        );
+        let json = report.to_json();
+
+        assert!(json.contains(r#""status":"failed""#));
+        assert!(json.contains(r#""error":"panicked: not yet implemented""#));
+        assert_eq!(from_json(&json), Some(report));
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert_eq!(from_json("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(from_json(r#"{"year":2022,"day":26}"#), None);
+    }
+
+    #[test]
+    fn parses_format() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
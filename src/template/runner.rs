@@ -1,70 +1,352 @@
+use crate::template::alloc::{self, Allocations};
+use crate::template::aoc_client::{self, Verdict};
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::report::{Format, PartReport};
+use crate::template::stats::{format_duration, Statistic, Stats, Summary};
+use crate::template::submissions::{self, Attempt};
+use crate::template::{answers, config, input, params, Puzzle, ANSI_ITALIC, ANSI_RESET};
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Flags that control how the parts of a solution are run and reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    pub format: Format,
+    pub statistic: Statistic,
+    /// Bench every stage according to this config, or run it once if [`None`].
+    pub bench: Option<BenchConfig>,
+    /// Print intermediate results and bench progress while a stage is running.
+    pub progress: bool,
+    /// Report a stage as failed if its first run takes longer than this, see
+    /// [`Entry::run_isolated`].
+    ///
+    /// [`Entry::run_isolated`]: crate::template::registry::Entry::run_isolated
+    pub timeout: Option<Duration>,
+}
+
+impl RunOptions {
+    /// Reads the options from the `--format`, `--stat`, `--time` and `--timeout` arguments passed
+    /// to the current process, as well as the [`BenchConfig`] arguments.
+    pub fn from_args() -> Self {
+        let format = get_arg("--format").unwrap_or_default();
+
+        Self {
+            format,
+            statistic: get_arg("--stat").unwrap_or_default(),
+            bench: env::args()
+                .any(|x| x == "--time")
+                .then(BenchConfig::from_args),
+            progress: format == Format::Human,
+            timeout: get_arg::<u64>("--timeout").map_or(config::get().timeout, |secs| {
+                (secs > 0).then(|| Duration::from_secs(secs))
+            }),
+        }
+    }
+}
+
+/// Run a solution part and return its answer along with a report of the run.
+/// A panic is reported as a failed part.
+pub fn solve_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    options: &RunOptions,
+) -> (Option<T>, PartReport) {
     let part_str = format!("Part {part}");
+    let timer = Instant::now();
+
+    let run = catch_panic(|| {
+        run_timed(func, input, options, |result| {
+            if options.progress {
+                print_result(result, &part_str, "");
+            }
+        })
+    });
+
+    match run {
+        Ok((result, stats, allocations)) => {
+            let answer = result.as_ref().map(ToString::to_string);
+            let report = PartReport {
+                allocations,
+                ..PartReport::new(puzzle, Some(part), answer, &stats)
+            };
+            (result, report)
+        }
+        Err(message) => (
+            None,
+            PartReport::failed(puzzle, Some(part), message, timer.elapsed()),
+        ),
+    }
+}
+
+/// Run the parse stage of a solution and return its output, which is then passed to each part.
+/// Returns no output if the stage panicked, in which case the parts cannot run.
+pub fn solve_parse<'a, P>(
+    func: impl Fn(&'a str) -> P,
+    input: &'a str,
+    puzzle: Puzzle,
+    options: &RunOptions,
+) -> (Option<P>, PartReport) {
+    let timer = Instant::now();
+
+    let run = catch_panic(|| {
+        run_timed(func, input, options, |_| {
+            if options.progress {
+                print!("Parse: ✔");
+            }
+        })
+    });
+
+    match run {
+        Ok((parsed, stats, allocations)) => {
+            let report = PartReport {
+                allocations,
+                ..PartReport::new(puzzle, None, None, &stats)
+            };
+            (Some(parsed), report)
+        }
+        Err(message) => (
+            None,
+            PartReport::failed(puzzle, None, message, timer.elapsed()),
+        ),
+    }
+}
+
+thread_local! {
+    /// Called when the first run of a stage on this thread has finished, see [`on_first_run`].
+    static ON_FIRST_RUN: RefCell<Option<Box<dyn Fn()>>> = const { RefCell::new(None) };
+    /// Whether a panic on this thread is caught by [`catch_panic`].
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// The message of the last panic caught on this thread, including its location.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Calls `callback` whenever a stage that runs on the current thread has finished its first,
+/// untimed run, i.e. before it is warmed up and benched.
+pub fn on_first_run(callback: impl Fn() + 'static) {
+    ON_FIRST_RUN.set(Some(Box::new(callback)));
+}
+
+/// Runs `func` and returns the message of its panic as an error, e.g. `panicked: not yet
+/// implemented at src/bin/10.rs:57:18`. The message is not printed by the panic hook.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING.get() {
+                let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+                let message = match info.location() {
+                    Some(location) => format!("panicked: {message} at {location}"),
+                    None => format!("panicked: {message}"),
+                };
+                LAST_PANIC.set(Some(message));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = IS_CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.set(was_catching);
+
+    result.map_err(|_| LAST_PANIC.take().unwrap_or_else(|| "panicked".into()))
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Handles a report in a solution binary: prints it and, if `--record` or `--submit` were passed,
+/// records or submits the answer.
+pub fn handle_report(report: &PartReport, puzzle: Puzzle, options: &RunOptions) {
+    print_report(report, options.format, options.statistic);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (Some(part), Some(answer)) = (report.part, &report.answer) else {
+        return;
+    };
+
+    let reason = if params::is_overridden() {
+        Some("its parameters were overridden")
+    } else if input::Source::from_args() != input::Source::Puzzle {
+        Some("it did not run on the puzzle input")
+    } else {
+        None
+    };
+
+    if let Some(reason) = reason {
+        if env::args().any(|x| x == "--record" || x == "--submit") {
+            eprintln!("Not recording or submitting part {part}, {reason}.");
+        }
+        return;
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if env::args().any(|x| x == "--record") {
+        record_result(answer, puzzle, part, options.format);
+    }
+
+    if let Some(Verdict::Correct) = submit_result(answer, puzzle, part) {
+        record_result(answer, puzzle, part, options.format);
+    }
+}
+
+/// Prints the outcome of a stage, either as a human-readable line or as a line of JSON.
+pub fn print_report(report: &PartReport, format: Format, statistic: Statistic) {
+    match format {
+        Format::Json => println!("{}", report.to_json()),
+        Format::Human => {
+            let label = report.label();
+            let mut duration_str = format_elapsed(report.duration.get(statistic), report.samples);
+
+            if let Some(allocations) = report.allocations {
+                // insert the allocations into the parentheses around the duration.
+                duration_str.insert_str(duration_str.len() - 1, &format!(" · {allocations}"));
+            }
+
+            if let Some(error) = &report.error {
+                print!("\r");
+                println!("{label}: ✖ {error}");
+            } else if report.part.is_some() {
+                print_result(&report.answer, &label, &duration_str);
+            } else {
+                print!("\r");
+                println!("{label}: ✔{duration_str}");
+            }
+
+            if report.samples > 1 {
+                print_summary(&report.duration);
+            }
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run a solution stage. The behavior differs depending on whether a [`BenchConfig`] is passed:
+///  1. by default, the function is executed once.
+///  2. with a config, the function is warmed up and then benched according to it.
+///
+/// The allocations are counted for the first run, with the `count-allocations` feature.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Stats, Option<Allocations>) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocations) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
+    ON_FIRST_RUN.with_borrow(|callback| {
+        if let Some(callback) = callback {
+            callback();
+        }
+    });
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let stats = match &options.bench {
+        Some(config) => bench(func, input, base_time, config, options.progress),
+        None => Stats::new(vec![base_time]),
     };
 
-    (result, run.0, run.1)
+    (result, stats, allocations)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Controls how long a part is benched for when running with `--time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Time spent running the part before any samples are recorded.
+    pub warmup: Duration,
+    /// Approximate total time spent collecting samples.
+    pub target: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(100),
+            target: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+impl BenchConfig {
+    /// Reads the config from the `--warmup`, `--bench-time`, `--min-samples` and `--max-samples`
+    /// arguments passed to the current process. Durations are given in milliseconds.
+    /// Missing arguments fall back to the budget in `aoc.toml`.
+    pub fn from_args() -> Self {
+        let default = config::get().bench;
+
+        Self {
+            warmup: get_arg("--warmup").map_or(default.warmup, Duration::from_millis),
+            target: get_arg("--bench-time").map_or(default.target, Duration::from_millis),
+            min_samples: get_arg("--min-samples").unwrap_or(default.min_samples),
+            max_samples: get_arg("--max-samples").unwrap_or(default.max_samples),
+        }
+    }
+
+    /// The arguments that need to be passed to a solution binary to apply this config.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--warmup".into(),
+            self.warmup.as_millis().to_string(),
+            "--bench-time".into(),
+            self.target.as_millis().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+
+    /// Number of samples to collect for a function taking roughly `base_time` per run.
+    fn iterations(&self, base_time: Duration) -> u128 {
+        let min_samples = self.min_samples.max(1);
+        (self.target.as_nanos() / base_time.as_nanos().max(10))
+            .clamp(min_samples, self.max_samples.max(min_samples))
+    }
+}
+
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: Duration,
+    config: &BenchConfig,
+    show_progress: bool,
+) -> Stats {
+    let progress = |step: &str| {
+        if show_progress {
+            print!(" > {ANSI_ITALIC}{step}{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+    };
+
+    progress("warming up");
+
+    let warmup = warm_up(&func, &input, config.warmup).unwrap_or(base_time);
+
+    progress("benching");
+
+    let bench_iterations = config.iterations(warmup);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,29 +358,69 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::new(timers)
+}
+
+/// Runs the function until `duration` has passed and returns the average time per run,
+/// or [`None`] if no warm-up was requested.
+fn warm_up<I: Clone, T>(func: &impl Fn(I) -> T, input: &I, duration: Duration) -> Option<Duration> {
+    if duration.is_zero() {
+        return None;
+    }
+
+    let timer = Instant::now();
+    let mut runs: u32 = 0;
+
+    while timer.elapsed() < duration {
+        black_box(func(black_box(input.clone())));
+        runs += 1;
+    }
+
+    Some(timer.elapsed() / runs.max(1))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Reads the value following `name` from the arguments passed to the current process.
+pub(crate) fn get_arg<T: FromStr>(name: &str) -> Option<T>
+where
+    T::Err: Display,
+{
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == name)?;
+
+    match args.get(index + 1).map(|x| x.parse::<T>()) {
+        Some(Ok(value)) => Some(value),
+        Some(Err(e)) => {
+            eprintln!("Unexpected value for {name}: {e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input: {name} expects a value.");
+            process::exit(1);
+        }
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_elapsed(duration: Duration, samples: usize) -> String {
     if samples == 1 {
-        format!(" ({duration:.1?})")
+        format!(" ({})", format_duration(duration))
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({} @ {samples} samples)", format_duration(duration))
     }
 }
 
+fn print_summary(summary: &Summary) {
+    println!(
+        "{ANSI_ITALIC}  mean {} · median {} · min {} · max {} · σ {} · p95 {} · p99 {}{ANSI_RESET}",
+        format_duration(summary.mean),
+        format_duration(summary.median),
+        format_duration(summary.min),
+        format_duration(summary.max),
+        format_duration(summary.stddev),
+        format_duration(summary.p95),
+        format_duration(summary.p99),
+    );
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -134,14 +456,24 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+/// Store the answer of a part as its expected answer in `data/answers/`.
+fn record_result(answer: &str, puzzle: Puzzle, part: u8, format: Format) {
+    match answers::record(puzzle, part, answer) {
+        Ok(()) => {
+            if format == Format::Human {
+                println!(
+                    "Recorded answer for part {part} in \"{}\".",
+                    answers::get_path(puzzle)
+                );
+            }
+        }
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution.
+/// Returns the verdict of the server if the part was submitted.
+fn submit_result(answer: &str, puzzle: Puzzle, part: u8) -> Option<Verdict> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -164,11 +496,30 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let history = submissions::load(puzzle).unwrap_or_else(|e| {
+        eprintln!("Failed to load submission history: {e}");
         process::exit(1);
+    });
+
+    if let Err(refusal) = history.check(part, answer) {
+        eprintln!("Not submitting {answer}: {refusal}");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+
+    match aoc_client::submit(puzzle, part, answer) {
+        Ok(verdict) => {
+            println!("{verdict}");
+            if let Err(e) = submissions::record(puzzle, Attempt::new(part, answer, verdict.clone()))
+            {
+                eprintln!("Failed to record submission: {e}");
+            }
+            Some(verdict)
+        }
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,166 @@
+/// The [`Solution`] trait, which every solution runs through. Days that are made of free `part_one`
+/// and `part_two` functions implement it with a hidden type that the `solution!` macro generates.
+use std::fmt::Display;
+
+use crate::template::params;
+use crate::template::report::PartReport;
+use crate::template::runner::{solve_parse, solve_part, RunOptions};
+use crate::template::{Day, Puzzle};
+
+/// A solution of a puzzle. Register an implementation with `solution!(impl <type>)`.
+///
+/// ```ignore
+/// pub struct Day05;
+///
+/// impl Solution for Day05 {
+///     const DAY: Day = day!(5);
+///     const TITLE: Option<&'static str> = Some("If You Give A Seed A Fertilizer");
+///     const TAGS: &'static [&'static str] = &["ranges"];
+///
+///     type Parsed<'a> = Almanac;
+///     type Answer1 = u64;
+///     type Answer2 = u64;
+///
+///     fn parse(&self, input: &str) -> Almanac { ... }
+///     fn part_one(&self, almanac: &Almanac) -> Option<u64> { ... }
+///     fn part_two(&self, almanac: &Almanac) -> Option<u64> { ... }
+/// }
+///
+/// advent_of_code::solution!(impl Day05);
+/// ```
+pub trait Solution {
+    const DAY: Day;
+
+    /// The title of the puzzle, shown by `cargo all`.
+    const TITLE: Option<&'static str> = None;
+
+    /// Free-form labels of the puzzle, e.g. the techniques it takes.
+    const TAGS: &'static [&'static str] = &[];
+
+    /// Whether [`parse`](Self::parse) is timed and reported as a stage of its own.
+    /// If `false`, it runs once before the parts, e.g. if it only passes the input through.
+    const PARSE_STAGE: bool = true;
+
+    /// The parts that are run, e.g. `&[1]` to leave out part two.
+    const PARTS: &'static [u8] = &[1, 2];
+
+    /// The input as passed to both parts. Use `&'a str` to pass the raw input.
+    type Parsed<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse<'a>(&self, input: &'a str) -> Self::Parsed<'a>;
+
+    fn part_one(&self, _input: &Self::Parsed<'_>) -> Option<Self::Answer1> {
+        None
+    }
+
+    fn part_two(&self, _input: &Self::Parsed<'_>) -> Option<Self::Answer2> {
+        None
+    }
+}
+
+/// Runs every stage of a solution on an input of `puzzle` and passes each report to the callback.
+pub fn run<S: Solution>(
+    solution: &S,
+    puzzle: Puzzle,
+    input: &str,
+    options: &RunOptions,
+    on_report: &mut dyn FnMut(&PartReport),
+) {
+    params::load();
+
+    let parsed = if S::PARSE_STAGE {
+        let (parsed, report) = solve_parse(|x| solution.parse(x), input, puzzle, options);
+        on_report(&report);
+        match parsed {
+            Some(parsed) => parsed,
+            None => return,
+        }
+    } else {
+        solution.parse(input)
+    };
+
+    for &part in S::PARTS {
+        let report = match part {
+            1 => solve_part(|x| solution.part_one(x), &parsed, puzzle, 1, options).1,
+            2 => solve_part(|x| solution.part_two(x), &parsed, puzzle, 2, options).1,
+            _ => continue,
+        };
+        on_report(&report);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A `parse` function, whose output may borrow from the input. Lets the type that `solution!`
+/// generates for free functions name their parsed input as [`Solution::Parsed`].
+pub trait ParseFn<'a> {
+    type Output;
+
+    fn call(&self, input: &'a str) -> Self::Output;
+}
+
+impl<'a, F, P> ParseFn<'a> for F
+where
+    F: Fn(&'a str) -> P,
+{
+    type Output = P;
+
+    fn call(&self, input: &'a str) -> P {
+        self(input)
+    }
+}
+
+/// Stands in for a part that `solution!` leaves out, e.g. part two of `solution!(1, 1)`.
+pub fn unsolved<T: ?Sized>(_input: &T) -> Option<u8> {
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Solution;
+    use crate::day;
+    use crate::template::registry::Entry;
+    use crate::template::report::{PartReport, Status};
+    use crate::template::runner::RunOptions;
+    use crate::template::{Day, Puzzle};
+
+    /// Counts the words of the input, which it borrows.
+    struct Words;
+
+    impl Solution for Words {
+        const DAY: Day = day!(3);
+        const TITLE: Option<&'static str> = Some("Words");
+        const PARTS: &'static [u8] = &[2];
+
+        type Parsed<'a> = Vec<&'a str>;
+        type Answer1 = usize;
+        type Answer2 = String;
+
+        fn parse<'a>(&self, input: &'a str) -> Vec<&'a str> {
+            input.split_whitespace().collect()
+        }
+
+        fn part_two(&self, words: &Vec<&str>) -> Option<String> {
+            words.last().map(ToString::to_string)
+        }
+    }
+
+    const WORDS: Entry = Entry::of(&Words, "src/bin/03.rs");
+
+    #[test]
+    fn runs_the_stages_of_a_solution() {
+        let mut reports: Vec<PartReport> = vec![];
+        WORDS.run("a b c", &RunOptions::default(), &mut |x| {
+            reports.push(x.clone());
+        });
+
+        let stages: Vec<_> = reports.iter().map(|x| (x.part, x.status)).collect();
+        assert_eq!(stages, [(None, Status::Ok), (Some(2), Status::Ok)]);
+        assert_eq!(reports[1].answer.as_deref(), Some("c"));
+        assert_eq!((WORDS.day(), WORDS.title()), (day!(3), Some("Words")));
+    }
+}
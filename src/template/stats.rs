@@ -0,0 +1,343 @@
+/// Summary statistics over the samples collected while benchmarking a solution part.
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A set of benchmark samples, kept in ascending order.
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    samples: Vec<Duration>,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        Self { samples }
+    }
+
+    /// All samples, sorted from fastest to slowest.
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.is_empty() {
+            return Duration::ZERO;
+        }
+        let total: u128 = self.samples.iter().map(Duration::as_nanos).sum();
+        from_nanos(total / self.samples.len() as u128)
+    }
+
+    pub fn median(&self) -> Duration {
+        let n = self.samples.len();
+        match n {
+            0 => Duration::ZERO,
+            _ if n % 2 == 1 => self.samples[n / 2],
+            _ => (self.samples[n / 2 - 1] + self.samples[n / 2]) / 2,
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.first().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.last().copied().unwrap_or_default()
+    }
+
+    /// Population standard deviation of the samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn stddev(&self) -> Duration {
+        if self.samples.len() < 2 {
+            return Duration::ZERO;
+        }
+        let mean = self.mean().as_nanos() as f64;
+        let variance = self
+            .samples
+            .iter()
+            .map(|x| (x.as_nanos() as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.samples.len() as f64;
+        Duration::from_secs_f64(variance.sqrt() / 1e9)
+    }
+
+    /// Nearest-rank percentile, `p` being a value between 0 and 100.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.is_empty() {
+            return Duration::ZERO;
+        }
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * self.samples.len() as f64).ceil() as usize;
+        self.samples[rank.saturating_sub(1)]
+    }
+
+    /// Returns the value of the given [`Statistic`].
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Mean => self.mean(),
+            Statistic::Median => self.median(),
+            Statistic::Min => self.min(),
+            Statistic::Max => self.max(),
+            Statistic::StdDev => self.stddev(),
+            Statistic::P95 => self.percentile(95.0),
+            Statistic::P99 => self.percentile(99.0),
+        }
+    }
+
+    /// Computes every [`Statistic`] at once.
+    pub fn summary(&self) -> Summary {
+        Summary {
+            mean: self.mean(),
+            median: self.median(),
+            min: self.min(),
+            max: self.max(),
+            stddev: self.stddev(),
+            p95: self.percentile(95.0),
+            p99: self.percentile(99.0),
+        }
+    }
+}
+
+/// The value of every [`Statistic`] for a set of samples. Serializes durations as nanoseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    #[serde(rename = "mean_ns", with = "nanos")]
+    pub mean: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "max_ns", with = "nanos")]
+    pub max: Duration,
+    #[serde(rename = "stddev_ns", with = "nanos")]
+    pub stddev: Duration,
+    #[serde(rename = "p95_ns", with = "nanos")]
+    pub p95: Duration,
+    #[serde(rename = "p99_ns", with = "nanos")]
+    pub p99: Duration,
+}
+
+impl Summary {
+    /// Returns the value of the given [`Statistic`].
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Mean => self.mean,
+            Statistic::Median => self.median,
+            Statistic::Min => self.min,
+            Statistic::Max => self.max,
+            Statistic::StdDev => self.stddev,
+            Statistic::P95 => self.p95,
+            Statistic::P99 => self.p99,
+        }
+    }
+}
+
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(super::to_nanos(*duration))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+fn to_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Formats a duration with three significant digits in the largest unit that keeps it at or
+/// above 1, e.g. `74ns`, `1.21µs`, `120µs` or `2.80ms`. Unlike `{:?}`, every value is as precise.
+pub fn format_duration(duration: Duration) -> String {
+    const UNITS: [(&str, f64); 4] = [("s", 1e9), ("ms", 1e6), ("µs", 1e3), ("ns", 1.0)];
+
+    #[allow(clippy::cast_precision_loss)]
+    let nanos = duration.as_nanos() as f64;
+
+    // values that round up to 1000 are shown in the next unit, e.g. `1.00ms` for `999.7µs`.
+    let (unit, scale) = UNITS
+        .into_iter()
+        .find(|(_, scale)| nanos >= scale * 0.9995)
+        .unwrap_or(UNITS[3]);
+
+    let value = nanos / scale;
+    let decimals = match value {
+        _ if unit == "ns" => 0,
+        x if x < 9.995 => 2,
+        x if x < 99.95 => 1,
+        _ => 0,
+    };
+
+    format!("{value:.decimals$}{unit}")
+}
+
+fn from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The statistic that is reported as "the" timing of a part, e.g. in the readme table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Statistic {
+    #[default]
+    Mean,
+    Median,
+    Min,
+    Max,
+    StdDev,
+    P95,
+    P99,
+}
+
+impl Statistic {
+    pub const ALL: [Statistic; 7] = [
+        Statistic::Mean,
+        Statistic::Median,
+        Statistic::Min,
+        Statistic::Max,
+        Statistic::StdDev,
+        Statistic::P95,
+        Statistic::P99,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Statistic::Mean => "mean",
+            Statistic::Median => "median",
+            Statistic::Min => "min",
+            Statistic::Max => "max",
+            Statistic::StdDev => "stddev",
+            Statistic::P95 => "p95",
+            Statistic::P99 => "p99",
+        }
+    }
+}
+
+impl Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Statistic {
+    type Err = StatisticFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.as_str().eq_ignore_ascii_case(s))
+            .ok_or(StatisticFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Statistic`].
+#[derive(Debug)]
+pub struct StatisticFromStrError;
+
+impl Error for StatisticFromStrError {}
+
+impl Display for StatisticFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of: mean, median, min, max, stddev, p95, p99")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_duration, Statistic, Stats};
+    use std::time::Duration;
+
+    fn get_mock_stats() -> Stats {
+        Stats::new((1..=100).rev().map(Duration::from_nanos).collect())
+    }
+
+    #[test]
+    fn sorts_samples() {
+        let stats = get_mock_stats();
+        assert_eq!(stats.samples().first(), Some(&Duration::from_nanos(1)));
+        assert_eq!(stats.samples().last(), Some(&Duration::from_nanos(100)));
+    }
+
+    #[test]
+    fn computes_summary() {
+        let stats = get_mock_stats();
+        assert_eq!(stats.len(), 100);
+        assert_eq!(stats.min(), Duration::from_nanos(1));
+        assert_eq!(stats.max(), Duration::from_nanos(100));
+        assert_eq!(stats.mean(), Duration::from_nanos(50));
+        assert_eq!(stats.median(), Duration::from_nanos(50));
+        assert_eq!(stats.percentile(95.0), Duration::from_nanos(95));
+        assert_eq!(stats.percentile(99.0), Duration::from_nanos(99));
+        assert_eq!(stats.stddev().as_nanos(), 29);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::new(vec![Duration::from_millis(3)]);
+        for statistic in Statistic::ALL {
+            let expected = match statistic {
+                Statistic::StdDev => Duration::ZERO,
+                _ => Duration::from_millis(3),
+            };
+            assert_eq!(stats.get(statistic), expected);
+        }
+    }
+
+    #[test]
+    fn summary_matches_stats() {
+        let stats = get_mock_stats();
+        let summary = stats.summary();
+        for statistic in Statistic::ALL {
+            assert_eq!(summary.get(statistic), stats.get(statistic));
+        }
+    }
+
+    #[test]
+    fn serializes_summary_as_nanos() {
+        let summary = Stats::new(vec![Duration::from_micros(2)]).summary();
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(json.contains("\"mean_ns\":2000"), true);
+        assert_eq!(
+            serde_json::from_str::<super::Summary>(&json).unwrap(),
+            summary
+        );
+    }
+
+    #[test]
+    fn formats_durations_with_three_digits() {
+        assert_eq!(format_duration(Duration::from_nanos(74)), "74ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_214)), "1.21µs");
+        assert_eq!(format_duration(Duration::from_nanos(120_140)), "120µs");
+        assert_eq!(format_duration(Duration::from_nanos(999_700)), "1.00ms");
+        assert_eq!(format_duration(Duration::from_micros(2_800)), "2.80ms");
+        assert_eq!(format_duration(Duration::from_millis(74_130)), "74.1s");
+        assert_eq!(format_duration(Duration::ZERO), "0ns");
+    }
+
+    #[test]
+    fn parses_statistic() {
+        assert_eq!("median".parse::<Statistic>().unwrap(), Statistic::Median);
+        assert_eq!("P95".parse::<Statistic>().unwrap(), Statistic::P95);
+        assert!("average".parse::<Statistic>().is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use std::path::Path;
+/// Keeps a log of every answer submitted to Advent of Code in `data/submissions/`,
+/// so answers that are known to be wrong are not submitted again.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::aoc_client::Verdict;
+use crate::template::json_store::{self, Error};
+use crate::template::Puzzle;
+
+/// A single submitted answer and the server's response to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+}
+
+impl Attempt {
+    pub fn new(part: u8, answer: &str, verdict: Verdict) -> Self {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            part,
+            answer: answer.into(),
+            verdict,
+            submitted_at,
+        }
+    }
+
+    /// Whether the server checked the answer and did not accept it.
+    pub fn is_rejection(&self) -> bool {
+        matches!(
+            self.verdict,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+/// All attempts of a day, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+/// The reason why an answer should not be submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved, contains the accepted answer.
+    AlreadySolved(String),
+    /// The exact answer was submitted before and rejected.
+    AlreadyRejected(Verdict),
+    /// The answer is less than or equal to an answer that was too low.
+    TooLow(String),
+    /// The answer is greater than or equal to an answer that was too high.
+    TooHigh(String),
+}
+
+impl std::fmt::Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::AlreadyRejected(verdict) => {
+                write!(f, "this answer was already submitted: {verdict}")
+            }
+            Refusal::TooLow(bound) => {
+                write!(f, "this answer is too low, {bound} was already too low.")
+            }
+            Refusal::TooHigh(bound) => {
+                write!(f, "this answer is too high, {bound} was already too high.")
+            }
+        }
+    }
+}
+
+impl History {
+    pub fn for_part(&self, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| a.part == part)
+    }
+
+    /// Checks `answer` against the previous attempts of the part.
+    /// Numeric answers are additionally checked against the known too-low and too-high bounds.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        if let Some(solved) = self.for_part(part).find(|a| a.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(solved.answer.clone()));
+        }
+
+        if let Some(rejected) = self
+            .for_part(part)
+            .find(|a| a.is_rejection() && a.answer == answer)
+        {
+            return Err(Refusal::AlreadyRejected(rejected.verdict.clone()));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        for attempt in self.for_part(part) {
+            let Ok(bound) = attempt.answer.parse::<i128>() else {
+                continue;
+            };
+
+            match attempt.verdict {
+                Verdict::TooLow if value <= bound => {
+                    return Err(Refusal::TooLow(attempt.answer.clone()))
+                }
+                Verdict::TooHigh if value >= bound => {
+                    return Err(Refusal::TooHigh(attempt.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: Puzzle) -> String {
+    puzzle.data_path("submissions", ".json")
+}
+
+/// Loads the submission history of a day. Returns an empty [`History`] if nothing was submitted yet.
+pub fn load(puzzle: Puzzle) -> Result<History, Error> {
+    json_store::load(Path::new(&get_path(puzzle)))
+}
+
+pub fn save(puzzle: Puzzle, history: &History) -> Result<(), Error> {
+    json_store::save(Path::new(&get_path(puzzle)), history)
+}
+
+/// Appends an attempt to the history of a day.
+pub fn record(puzzle: Puzzle, attempt: Attempt) -> Result<(), Error> {
+    let mut history = load(puzzle)?;
+    history.attempts.push(attempt);
+    save(puzzle, &history)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Attempt, History, Refusal};
+    use crate::template::aoc_client::Verdict;
+
+    fn get_mock_history() -> History {
+        History {
+            attempts: vec![
+                Attempt::new(1, "100", Verdict::TooHigh),
+                Attempt::new(1, "10", Verdict::TooLow),
+                Attempt::new(1, "50", Verdict::Incorrect),
+                Attempt::new(1, "60", Verdict::RateLimited),
+                Attempt::new(2, "7", Verdict::Correct),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let history = get_mock_history();
+        assert_eq!(
+            history.check(1, "50"),
+            Err(Refusal::AlreadyRejected(Verdict::Incorrect))
+        );
+        assert_eq!(history.check(1, "60"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let history = get_mock_history();
+        assert_eq!(
+            history.check(1, "10"),
+            Err(Refusal::AlreadyRejected(Verdict::TooLow))
+        );
+        assert_eq!(history.check(1, "5"), Err(Refusal::TooLow("10".into())));
+        assert_eq!(history.check(1, "150"), Err(Refusal::TooHigh("100".into())));
+        assert_eq!(history.check(1, "11"), Ok(()));
+        assert_eq!(history.check(1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let history = get_mock_history();
+        assert_eq!(
+            history.check(2, "8"),
+            Err(Refusal::AlreadySolved("7".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = get_mock_history();
+        let json = serde_json::to_string(&history).unwrap();
+        assert!(json.contains(r#""verdict":"too_high""#));
+        assert_eq!(serde_json::from_str::<History>(&json).unwrap(), history);
+    }
+}